@@ -0,0 +1,49 @@
+use super::*;
+
+#[test]
+fn test_classify() {
+    let rtp = [
+        0x80, 0x60, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, 0x1c, 0x64, 0x27, 0x82,
+    ];
+    // marker bit set with payload type 96
+    let rtp_marker = [
+        0x80, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, 0x1c, 0x64, 0x27, 0x82,
+    ];
+    // receiver report, packet type 201
+    let rtcp_rr = [0x81, 0xc9, 0x00, 0x07, 0x90, 0x2f, 0x9e, 0x2e];
+    // sender report, packet type 200
+    let rtcp_sr = [0x80, 0xc8, 0x00, 0x06, 0x90, 0x2f, 0x9e, 0x2e];
+    // STUN binding request
+    let stun = [0x00, 0x01, 0x00, 0x00, 0x21, 0x12, 0xa4, 0x42];
+    // DTLS handshake record
+    let dtls = [0x16, 0xfe, 0xfd, 0x00, 0x00];
+
+    let tests: Vec<(&str, &[u8], PacketKind)> = vec![
+        ("Empty", &[], PacketKind::Unknown),
+        ("Rtp", &rtp, PacketKind::Rtp),
+        ("RtpMarker", &rtp_marker, PacketKind::Rtp),
+        ("RtpTooShort", &rtp[..11], PacketKind::Unknown),
+        ("RtcpReceiverReport", &rtcp_rr, PacketKind::Rtcp),
+        ("RtcpSenderReport", &rtcp_sr, PacketKind::Rtcp),
+        ("RtcpTooShort", &rtcp_sr[..4], PacketKind::Unknown),
+        ("Stun", &stun, PacketKind::Stun),
+        ("Zrtp", &[0x10, 0x00], PacketKind::Zrtp),
+        ("Dtls", &dtls, PacketKind::Dtls),
+        (
+            "TurnChannel",
+            &[0x40, 0x00, 0x00, 0x04],
+            PacketKind::TurnChannel,
+        ),
+        ("Unassigned", &[0x08, 0x00], PacketKind::Unknown),
+        ("RtpVersion3", &[0xc0; 12], PacketKind::Unknown),
+    ];
+
+    for (name, buf, kind) in tests {
+        assert_eq!(classify(buf), kind, "{}", name);
+    }
+
+    assert!(is_rtp(&rtp));
+    assert!(!is_rtp(&rtcp_rr));
+    assert!(is_rtcp(&rtcp_rr));
+    assert!(!is_rtcp(&stun));
+}
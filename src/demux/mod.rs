@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod demux_test;
+
+pub mod ssrc_demuxer;
+
+use crate::header::{CSRC_OFFSET, PT_MASK};
+
+/// RTCP packet types occupy payload types 64-95 once the marker bit is masked,
+/// see RFC 5761 section 4
+pub const RTCP_PAYLOAD_TYPE_MIN: u8 = 64;
+pub const RTCP_PAYLOAD_TYPE_MAX: u8 = 95;
+
+/// size of the RTCP header plus the sender SSRC, the smallest valid compound packet
+pub const RTCP_MIN_SIZE: usize = 8;
+
+/// PacketKind is the protocol of a datagram received on a multiplexed socket
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PacketKind {
+    Rtp,
+    Rtcp,
+    Stun,
+    Zrtp,
+    Dtls,
+    TurnChannel,
+    Unknown,
+}
+
+/// classify inspects the first two bytes of a datagram to tell RTP from RTCP
+/// (RFC 5761) and both from STUN, ZRTP, DTLS and TURN channel data (RFC 7983).
+///
+/// Format:
+/// ```text
+///                 +----------------+
+///                 |        [0..3] -+--> forward to STUN
+///                 |                |
+///                 |      [16..19] -+--> forward to ZRTP
+///                 |                |
+///     packet -->  |      [20..63] -+--> forward to DTLS
+///                 |                |
+///                 |      [64..79] -+--> forward to TURN Channel
+///                 |                |
+///                 |    [128..191] -+--> forward to RTP/RTCP
+///                 +----------------+
+/// ```
+pub fn classify(buf: &[u8]) -> PacketKind {
+    let b0 = match buf.first() {
+        Some(b0) => *b0,
+        None => return PacketKind::Unknown,
+    };
+
+    match b0 {
+        0..=3 => PacketKind::Stun,
+        16..=19 => PacketKind::Zrtp,
+        20..=63 => PacketKind::Dtls,
+        64..=79 => PacketKind::TurnChannel,
+        128..=191 => {
+            if buf.len() < 2 {
+                return PacketKind::Unknown;
+            }
+            let payload_type = buf[1] & PT_MASK;
+            if (RTCP_PAYLOAD_TYPE_MIN..=RTCP_PAYLOAD_TYPE_MAX).contains(&payload_type) {
+                if buf.len() >= RTCP_MIN_SIZE {
+                    PacketKind::Rtcp
+                } else {
+                    PacketKind::Unknown
+                }
+            } else if buf.len() >= CSRC_OFFSET {
+                PacketKind::Rtp
+            } else {
+                PacketKind::Unknown
+            }
+        }
+        _ => PacketKind::Unknown,
+    }
+}
+
+/// is_rtp reports whether the datagram is an RTP packet
+pub fn is_rtp(buf: &[u8]) -> bool {
+    classify(buf) == PacketKind::Rtp
+}
+
+/// is_rtcp reports whether the datagram is an RTCP packet
+pub fn is_rtcp(buf: &[u8]) -> bool {
+    classify(buf) == PacketKind::Rtcp
+}
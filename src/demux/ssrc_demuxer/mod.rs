@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod ssrc_demuxer_test;
+
+use crate::error::Result;
+use crate::packet::Packet;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::mpsc;
+
+/// StreamId identifies a stream registered to an SsrcDemuxer
+pub type StreamId = usize;
+
+/// StreamHandler receives the packets of a stream
+pub type StreamHandler = Box<dyn FnMut(Packet) + Send>;
+
+/// Demuxed is the outcome of demultiplexing a packet
+#[derive(Debug, PartialEq, Eq)]
+pub enum Demuxed {
+    /// the packet was delivered to the stream its SSRC is bound to
+    Delivered(StreamId),
+    /// the SSRC was unknown, it has been bound to the stream found by MID/RID
+    /// or payload type and the packet delivered. replaced is the SSRC the
+    /// stream was bound to before, if the stream changed SSRC.
+    Bound {
+        stream: StreamId,
+        ssrc: u32,
+        replaced: Option<u32>,
+    },
+    /// no stream matches the packet, which is given back
+    UnknownSsrc(Packet),
+}
+
+/// SsrcDemuxer delivers packets to stream handlers by SSRC, falling back to the
+/// MID and RID header extensions, then to the payload type, for unsignalled SSRCs
+#[derive(Default)]
+pub struct SsrcDemuxer {
+    mid_extension_id: Option<u8>,
+    rid_extension_id: Option<u8>,
+
+    next_stream_id: StreamId,
+    handlers: HashMap<StreamId, StreamHandler>,
+    ssrcs: HashMap<u32, StreamId>,
+    stream_ssrcs: HashMap<StreamId, u32>,
+    mids: HashMap<(String, Option<String>), StreamId>,
+    payload_types: HashMap<u8, StreamId>,
+}
+
+impl fmt::Debug for SsrcDemuxer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SsrcDemuxer")
+            .field("mid_extension_id", &self.mid_extension_id)
+            .field("rid_extension_id", &self.rid_extension_id)
+            .field("ssrcs", &self.ssrcs)
+            .field("mids", &self.mids)
+            .field("payload_types", &self.payload_types)
+            .finish()
+    }
+}
+
+impl SsrcDemuxer {
+    pub fn new() -> Self {
+        SsrcDemuxer::default()
+    }
+
+    /// set_mid_extension_id sets the id negotiated for the sdes:mid extension
+    pub fn set_mid_extension_id(&mut self, id: u8) {
+        self.mid_extension_id = Some(id);
+    }
+
+    /// set_rid_extension_id sets the id negotiated for the sdes:rtp-stream-id extension
+    pub fn set_rid_extension_id(&mut self, id: u8) {
+        self.rid_extension_id = Some(id);
+    }
+
+    /// add_stream registers a stream whose packets are given to handler
+    pub fn add_stream(&mut self, handler: StreamHandler) -> StreamId {
+        let stream = self.next_stream_id;
+        self.next_stream_id += 1;
+        self.handlers.insert(stream, handler);
+        stream
+    }
+
+    /// add_stream_channel registers a stream whose packets are sent to the
+    /// returned receiver
+    pub fn add_stream_channel(&mut self) -> (StreamId, mpsc::Receiver<Packet>) {
+        let (tx, rx) = mpsc::channel();
+        let stream = self.add_stream(Box::new(move |packet| {
+            let _ = tx.send(packet);
+        }));
+        (stream, rx)
+    }
+
+    /// remove_stream unregisters a stream and all its routes
+    pub fn remove_stream(&mut self, stream: StreamId) {
+        self.handlers.remove(&stream);
+        self.ssrcs.retain(|_, s| *s != stream);
+        self.stream_ssrcs.remove(&stream);
+        self.mids.retain(|_, s| *s != stream);
+        self.payload_types.retain(|_, s| *s != stream);
+    }
+
+    /// bind_ssrc routes packets of a signalled SSRC to stream
+    pub fn bind_ssrc(&mut self, ssrc: u32, stream: StreamId) {
+        self.ssrcs.insert(ssrc, stream);
+        self.stream_ssrcs.insert(stream, ssrc);
+    }
+
+    /// bind_mid routes packets of unknown SSRCs carrying mid, and rid if given,
+    /// to stream
+    pub fn bind_mid(&mut self, mid: &str, rid: Option<&str>, stream: StreamId) {
+        self.mids
+            .insert((mid.to_owned(), rid.map(str::to_owned)), stream);
+    }
+
+    /// bind_payload_type routes packets of unknown SSRCs without a known MID to
+    /// stream by payload type
+    pub fn bind_payload_type(&mut self, payload_type: u8, stream: StreamId) {
+        self.payload_types.insert(payload_type, stream);
+    }
+
+    /// ssrc returns the SSRC a stream is bound to
+    pub fn ssrc(&self, stream: StreamId) -> Option<u32> {
+        self.stream_ssrcs.get(&stream).copied()
+    }
+
+    /// demux delivers a packet to its stream. Parsing a malformed MID or RID
+    /// extension is an error.
+    pub fn demux(&mut self, packet: Packet) -> Result<Demuxed> {
+        let ssrc = packet.header.ssrc;
+        if let Some(stream) = self.ssrcs.get(&ssrc).copied() {
+            return Ok(match self.handlers.get_mut(&stream) {
+                Some(handler) => {
+                    handler(packet);
+                    Demuxed::Delivered(stream)
+                }
+                None => Demuxed::UnknownSsrc(packet),
+            });
+        }
+
+        let stream = match self.find_stream(&packet)? {
+            Some(stream) if self.handlers.contains_key(&stream) => stream,
+            _ => return Ok(Demuxed::UnknownSsrc(packet)),
+        };
+
+        let replaced = self.stream_ssrcs.get(&stream).copied();
+        if let Some(replaced) = replaced {
+            self.ssrcs.remove(&replaced);
+        }
+        self.bind_ssrc(ssrc, stream);
+
+        if let Some(handler) = self.handlers.get_mut(&stream) {
+            handler(packet);
+        }
+        Ok(Demuxed::Bound {
+            stream,
+            ssrc,
+            replaced,
+        })
+    }
+
+    fn find_stream(&self, packet: &Packet) -> Result<Option<StreamId>> {
+        if let Some(mid_id) = self.mid_extension_id {
+            if let Some(mid) = packet.header.get_mid(mid_id)? {
+                let rid = match self.rid_extension_id {
+                    Some(rid_id) => packet.header.get_rid(rid_id)?.map(|rid| rid.rid),
+                    None => None,
+                };
+                if rid.is_some() {
+                    if let Some(stream) = self.mids.get(&(mid.mid.clone(), rid)) {
+                        return Ok(Some(*stream));
+                    }
+                }
+                if let Some(stream) = self.mids.get(&(mid.mid, None)) {
+                    return Ok(Some(*stream));
+                }
+            }
+        }
+
+        Ok(self.payload_types.get(&packet.header.payload_type).copied())
+    }
+}
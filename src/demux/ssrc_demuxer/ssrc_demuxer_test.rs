@@ -0,0 +1,176 @@
+use super::*;
+use crate::error::Error;
+use crate::extension::sdes_extension::*;
+use crate::header::Header;
+
+use bytes::{Bytes, BytesMut};
+use util::marshal::{Marshal, MarshalSize};
+
+const MID_ID: u8 = 1;
+const RID_ID: u8 = 2;
+
+fn packet(ssrc: u32, payload_type: u8) -> Packet {
+    Packet {
+        header: Header {
+            version: 2,
+            ssrc,
+            payload_type,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x01]),
+        ..Default::default()
+    }
+}
+
+fn set_extension<M: Marshal + MarshalSize>(packet: &mut Packet, id: u8, ext: M) {
+    let mut raw = BytesMut::with_capacity(ext.marshal_size());
+    raw.resize(ext.marshal_size(), 0);
+    ext.marshal_to(&mut raw).unwrap();
+    packet.header.set_extension(id, raw.freeze()).unwrap();
+}
+
+fn packet_with_mid(ssrc: u32, mid: &str, rid: Option<&str>) -> Packet {
+    let mut p = packet(ssrc, 96);
+    set_extension(&mut p, MID_ID, MidExtension::new(mid).unwrap());
+    if let Some(rid) = rid {
+        set_extension(&mut p, RID_ID, RtpStreamIdExtension::new(rid).unwrap());
+    }
+    p
+}
+
+#[test]
+fn test_ssrc_demuxer_signalled_ssrc() -> Result<()> {
+    let mut demuxer = SsrcDemuxer::new();
+    let (audio, audio_rx) = demuxer.add_stream_channel();
+    let (video, video_rx) = demuxer.add_stream_channel();
+    demuxer.bind_ssrc(0x1111, audio);
+    demuxer.bind_ssrc(0x2222, video);
+
+    assert_eq!(
+        demuxer.demux(packet(0x1111, 111))?,
+        Demuxed::Delivered(audio)
+    );
+    assert_eq!(
+        demuxer.demux(packet(0x2222, 96))?,
+        Demuxed::Delivered(video)
+    );
+    assert_eq!(
+        demuxer.demux(packet(0x1111, 111))?,
+        Demuxed::Delivered(audio)
+    );
+    assert_eq!(audio_rx.try_iter().count(), 2);
+    assert_eq!(video_rx.try_iter().count(), 1);
+
+    let unknown = packet(0x3333, 96);
+    assert_eq!(
+        demuxer.demux(unknown.clone())?,
+        Demuxed::UnknownSsrc(unknown)
+    );
+
+    demuxer.remove_stream(audio);
+    let p = packet(0x1111, 111);
+    assert_eq!(demuxer.demux(p.clone())?, Demuxed::UnknownSsrc(p));
+
+    Ok(())
+}
+
+#[test]
+fn test_ssrc_demuxer_mid_rid() -> Result<()> {
+    let mut demuxer = SsrcDemuxer::new();
+    demuxer.set_mid_extension_id(MID_ID);
+    demuxer.set_rid_extension_id(RID_ID);
+
+    let (low, low_rx) = demuxer.add_stream_channel();
+    let (high, high_rx) = demuxer.add_stream_channel();
+    let (audio, _audio_rx) = demuxer.add_stream_channel();
+    demuxer.bind_mid("0", Some("l"), low);
+    demuxer.bind_mid("0", Some("h"), high);
+    demuxer.bind_mid("1", None, audio);
+
+    assert_eq!(
+        demuxer.demux(packet_with_mid(0x1111, "0", Some("h")))?,
+        Demuxed::Bound {
+            stream: high,
+            ssrc: 0x1111,
+            replaced: None
+        }
+    );
+    assert_eq!(
+        demuxer.demux(packet_with_mid(0x2222, "0", Some("l")))?,
+        Demuxed::Bound {
+            stream: low,
+            ssrc: 0x2222,
+            replaced: None
+        }
+    );
+    assert_eq!(
+        demuxer.demux(packet_with_mid(0x3333, "1", Some("x")))?,
+        Demuxed::Bound {
+            stream: audio,
+            ssrc: 0x3333,
+            replaced: None
+        }
+    );
+
+    // once bound, packets without extensions are routed by SSRC
+    assert_eq!(demuxer.demux(packet(0x1111, 96))?, Demuxed::Delivered(high));
+    assert_eq!(high_rx.try_iter().count(), 2);
+    assert_eq!(low_rx.try_iter().count(), 1);
+
+    // the encoder restarted with a new SSRC
+    assert_eq!(
+        demuxer.demux(packet_with_mid(0x4444, "0", Some("h")))?,
+        Demuxed::Bound {
+            stream: high,
+            ssrc: 0x4444,
+            replaced: Some(0x1111)
+        }
+    );
+    assert_eq!(demuxer.ssrc(high), Some(0x4444));
+    let p = packet(0x1111, 96);
+    assert_eq!(demuxer.demux(p.clone())?, Demuxed::UnknownSsrc(p));
+
+    let p = packet_with_mid(0x5555, "2", None);
+    assert_eq!(demuxer.demux(p.clone())?, Demuxed::UnknownSsrc(p));
+
+    // a malformed MID is an error
+    let mut p = packet(0x6666, 96);
+    p.header
+        .set_extension(MID_ID, Bytes::from_static(b" "))
+        .unwrap();
+    match demuxer.demux(p) {
+        Err(Error::Util(err)) => assert_eq!(Error::SdesItemInvalidCharacter(b' '), err),
+        result => panic!("expected invalid MID, got {:?}", result),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_ssrc_demuxer_payload_type() -> Result<()> {
+    let mut demuxer = SsrcDemuxer::new();
+    let received = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let recorder = received.clone();
+    let video = demuxer.add_stream(Box::new(move |p: Packet| {
+        recorder.lock().unwrap().push(p.header.ssrc)
+    }));
+    demuxer.bind_payload_type(96, video);
+
+    assert_eq!(
+        demuxer.demux(packet(0x1111, 96))?,
+        Demuxed::Bound {
+            stream: video,
+            ssrc: 0x1111,
+            replaced: None
+        }
+    );
+    assert_eq!(
+        demuxer.demux(packet(0x1111, 97))?,
+        Demuxed::Delivered(video)
+    );
+    let p = packet(0x2222, 97);
+    assert_eq!(demuxer.demux(p.clone())?, Demuxed::UnknownSsrc(p));
+    assert_eq!(*received.lock().unwrap(), vec![0x1111, 0x1111]);
+
+    Ok(())
+}
@@ -29,8 +29,16 @@ pub enum Error {
     #[error("header extension id must be 0 for none RFC 5285 extensions")]
     ErrRfc3550headerIdrange,
 
+    #[error("mtu({0}) is too small for packet overhead({1})")]
+    ErrMtuTooSmall(usize, usize),
     #[error("packet is not large enough")]
     ErrShortPacket,
+    #[error("{1} is truncated at byte offset {0}")]
+    ErrFieldTruncated(usize, &'static str),
+    #[error("{1} is invalid at byte offset {0}")]
+    ErrFieldInvalid(usize, &'static str),
+    #[error("padding size({0}) must be between 1 and 255")]
+    ErrPaddingSizeInvalid(usize),
     #[error("invalid nil packet")]
     ErrNilPacket,
     #[error("too many PDiff")]
@@ -49,6 +57,34 @@ pub enum Error {
     HeaderExtensionPayloadNot32BitWords,
     #[error("audio level overflow")]
     AudioLevelOverflow,
+    #[error("color space range and chroma siting must be 3 or less")]
+    ColorSpaceFieldOverflow,
+    #[error("csrc audio level count({0}) does not match csrc count({1})")]
+    CsrcAudioLevelCountMismatch(usize, usize),
+    #[error("csrc audio levels must be 15 or less")]
+    CsrcAudioLevelTooManyLevels,
+    #[error("dependency descriptor template structure has not been received")]
+    DependencyDescriptorMissingStructure,
+    #[error("dependency descriptor template structure is invalid")]
+    DependencyDescriptorInvalidStructure,
+    #[error("dependency descriptor template id is not in the template structure")]
+    DependencyDescriptorInvalidTemplateId,
+    #[error("dependency descriptor frame dependencies do not match the template structure")]
+    DependencyDescriptorInvalidFrameDependencies,
+    #[error("transmission offset overflow")]
+    TransmissionOffsetOverflow,
+    #[error("video content type {0} is invalid")]
+    VideoContentTypeInvalid(u8),
+    #[error("video layers allocation is invalid")]
+    VideoLayersAllocationInvalid,
+    #[error("transport-cc feedback request sequence count overflow")]
+    TransportCcSequenceCountOverflow,
+    #[error("frame marking temporal layer id must be 7 or less")]
+    FrameMarkingTidOverflow,
+    #[error("SDES item must be between 1 and 255 bytes")]
+    SdesItemSizeInvalid,
+    #[error("SDES item contains invalid character {0:#04x}")]
+    SdesItemInvalidCharacter(u8),
     #[error("payload is not large enough")]
     PayloadIsNotLargeEnough,
     #[error("STAP-A declared size({0}) is larger than buffer({1})")]
@@ -0,0 +1,94 @@
+use super::*;
+use crate::error::Result;
+use bytes::{Bytes, BytesMut};
+
+#[test]
+fn test_color_space_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; COLOR_SPACE_EXTENSION_SIZE - 1][..];
+    let result = ColorSpaceExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_color_space_extension() -> Result<()> {
+    // BT.709 limited range, chroma sited left and top
+    let raw = Bytes::from_static(&[0x01, 0x01, 0x01, 0x15]);
+    let buf = &mut raw.clone();
+    let c1 = ColorSpaceExtension::unmarshal(buf)?;
+    let c2 = ColorSpaceExtension {
+        primaries: 1,
+        transfer: 1,
+        matrix: 1,
+        range: 1,
+        chroma_siting_horizontal: 1,
+        chroma_siting_vertical: 1,
+        hdr_metadata: None,
+    };
+    assert_eq!(c1, c2);
+
+    let mut dst = BytesMut::with_capacity(c2.marshal_size());
+    dst.resize(c2.marshal_size(), 0);
+    c2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_color_space_extension_hdr_metadata() -> Result<()> {
+    // BT.2020 with PQ transfer and full range
+    let raw = Bytes::from_static(&[
+        0x09, 0x10, 0x09, 0x20, // color space
+        0x03, 0xe8, 0x00, 0x32, // luminance max 1000, min 50
+        0x85, 0xa0, 0x39, 0x08, // primary r
+        0x21, 0x34, 0x9b, 0xaa, // primary g
+        0x19, 0x96, 0x08, 0xfc, // primary b
+        0x3d, 0x13, 0x40, 0x42, // white point
+        0x03, 0xe8, 0x01, 0x90, // light levels 1000, 400
+    ]);
+    let buf = &mut raw.clone();
+    let c1 = ColorSpaceExtension::unmarshal(buf)?;
+    let c2 = ColorSpaceExtension {
+        primaries: 9,
+        transfer: 16,
+        matrix: 9,
+        range: 2,
+        chroma_siting_horizontal: 0,
+        chroma_siting_vertical: 0,
+        hdr_metadata: Some(HdrMetadata {
+            primary_r: Chromaticity { x: 34208, y: 14600 },
+            primary_g: Chromaticity { x: 8500, y: 39850 },
+            primary_b: Chromaticity { x: 6550, y: 2300 },
+            white_point: Chromaticity { x: 15635, y: 16450 },
+            luminance_max: 1000,
+            luminance_min: 50,
+            max_content_light_level: 1000,
+            max_frame_average_light_level: 400,
+        }),
+    };
+    assert_eq!(c1, c2);
+
+    let mut dst = BytesMut::with_capacity(c2.marshal_size());
+    dst.resize(c2.marshal_size(), 0);
+    c2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_color_space_extension_overflow() -> Result<()> {
+    let c = ColorSpaceExtension {
+        range: 4,
+        ..Default::default()
+    };
+
+    let mut dst = BytesMut::with_capacity(c.marshal_size());
+    dst.resize(c.marshal_size(), 0);
+    let result = c.marshal_to(&mut dst);
+    assert_eq!(Error::ColorSpaceFieldOverflow, result.unwrap_err());
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod color_space_extension_test;
+
+use crate::error::Error;
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use bytes::{Buf, BufMut};
+
+pub const COLOR_SPACE_EXTENSION_SIZE: usize = 4;
+pub const COLOR_SPACE_EXTENSION_SIZE_WITH_HDR_METADATA: usize = 28;
+
+// denominators of the fixed point values of the HDR metadata
+pub const CHROMATICITY_DENOMINATOR: u32 = 50000;
+pub const LUMINANCE_MAX_DENOMINATOR: u32 = 1;
+pub const LUMINANCE_MIN_DENOMINATOR: u32 = 10000;
+
+/// Chromaticity is a CIE 1931 xy coordinate in units of 1/CHROMATICITY_DENOMINATOR
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct Chromaticity {
+    pub x: u16,
+    pub y: u16,
+}
+
+/// HdrMetadata is the SMPTE ST 2086 mastering display metadata and the
+/// CTA-861.3 content light levels of the stream
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct HdrMetadata {
+    pub primary_r: Chromaticity,
+    pub primary_g: Chromaticity,
+    pub primary_b: Chromaticity,
+    pub white_point: Chromaticity,
+    /// maximum luminance of the mastering display, in units of 1/LUMINANCE_MAX_DENOMINATOR cd/m2
+    pub luminance_max: u16,
+    /// minimum luminance of the mastering display, in units of 1/LUMINANCE_MIN_DENOMINATOR cd/m2
+    pub luminance_min: u16,
+    /// maximum content light level, in cd/m2
+    pub max_content_light_level: u16,
+    /// maximum frame average light level, in cd/m2
+    pub max_frame_average_light_level: u16,
+}
+
+/// ColorSpaceExtension is a extension payload format in
+/// http://www.webrtc.org/experiments/rtp-hdrext/color-space
+///
+/// primaries, transfer and matrix use the code points of ITU-T H.273.
+/// range and the chroma siting fields are 2 bits each.
+///
+/// Without HDR metadata (one byte header):
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | L = 3 |   primaries   |   transfer    |    matrix     |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |range+chr.sit. |
+/// +-+-+-+-+-+-+-+-+
+///
+/// With HDR metadata (two byte header):
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |      ID       |   length=28   |   primaries   |   transfer    |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |    matrix     |range+chr.sit. |         luminance_max         |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |         luminance_min         |            mastering_metadata.|
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |primary_r.x and .y             |            mastering_metadata.|
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |primary_g.x and .y             |            mastering_metadata.|
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |primary_b.x and .y             |            mastering_metadata.|
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |white.x and .y                 |    max_content_light_level    |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// | max_frame_average_light_level |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct ColorSpaceExtension {
+    pub primaries: u8,
+    pub transfer: u8,
+    pub matrix: u8,
+    pub range: u8,
+    pub chroma_siting_horizontal: u8,
+    pub chroma_siting_vertical: u8,
+    pub hdr_metadata: Option<HdrMetadata>,
+}
+
+impl Unmarshal for ColorSpaceExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < COLOR_SPACE_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        let has_hdr_metadata =
+            raw_packet.remaining() >= COLOR_SPACE_EXTENSION_SIZE_WITH_HDR_METADATA;
+
+        let primaries = raw_packet.get_u8();
+        let transfer = raw_packet.get_u8();
+        let matrix = raw_packet.get_u8();
+        let b = raw_packet.get_u8();
+
+        let hdr_metadata = if has_hdr_metadata {
+            let luminance_max = raw_packet.get_u16();
+            let luminance_min = raw_packet.get_u16();
+            let primary_r = read_chromaticity(raw_packet);
+            let primary_g = read_chromaticity(raw_packet);
+            let primary_b = read_chromaticity(raw_packet);
+            let white_point = read_chromaticity(raw_packet);
+            Some(HdrMetadata {
+                primary_r,
+                primary_g,
+                primary_b,
+                white_point,
+                luminance_max,
+                luminance_min,
+                max_content_light_level: raw_packet.get_u16(),
+                max_frame_average_light_level: raw_packet.get_u16(),
+            })
+        } else {
+            None
+        };
+
+        Ok(ColorSpaceExtension {
+            primaries,
+            transfer,
+            matrix,
+            range: (b >> 4) & 0x3,
+            chroma_siting_horizontal: (b >> 2) & 0x3,
+            chroma_siting_vertical: b & 0x3,
+            hdr_metadata,
+        })
+    }
+}
+
+impl MarshalSize for ColorSpaceExtension {
+    /// MarshalSize returns the size of the ColorSpaceExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        if self.hdr_metadata.is_some() {
+            COLOR_SPACE_EXTENSION_SIZE_WITH_HDR_METADATA
+        } else {
+            COLOR_SPACE_EXTENSION_SIZE
+        }
+    }
+}
+
+impl Marshal for ColorSpaceExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        let size = self.marshal_size();
+        if buf.remaining_mut() < size {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        if self.range > 3 || self.chroma_siting_horizontal > 3 || self.chroma_siting_vertical > 3 {
+            return Err(Error::ColorSpaceFieldOverflow.into());
+        }
+
+        buf.put_u8(self.primaries);
+        buf.put_u8(self.transfer);
+        buf.put_u8(self.matrix);
+        buf.put_u8(
+            self.range << 4 | self.chroma_siting_horizontal << 2 | self.chroma_siting_vertical,
+        );
+
+        if let Some(hdr) = &self.hdr_metadata {
+            buf.put_u16(hdr.luminance_max);
+            buf.put_u16(hdr.luminance_min);
+            for chromaticity in &[hdr.primary_r, hdr.primary_g, hdr.primary_b, hdr.white_point] {
+                buf.put_u16(chromaticity.x);
+                buf.put_u16(chromaticity.y);
+            }
+            buf.put_u16(hdr.max_content_light_level);
+            buf.put_u16(hdr.max_frame_average_light_level);
+        }
+
+        Ok(size)
+    }
+}
+
+fn read_chromaticity<B>(raw_packet: &mut B) -> Chromaticity
+where
+    B: Buf,
+{
+    let x = raw_packet.get_u16();
+    let y = raw_packet.get_u16();
+    Chromaticity { x, y }
+}
@@ -0,0 +1,77 @@
+use super::*;
+use crate::error::Result;
+use bytes::{Bytes, BytesMut};
+
+#[test]
+fn test_csrc_audio_level_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 0][..];
+    let result = CsrcAudioLevelExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_csrc_audio_level_extension() -> Result<()> {
+    let raw = Bytes::from_static(&[0x00, 0x08, 0x7f]);
+    let buf = &mut raw.clone();
+    let a1 = CsrcAudioLevelExtension::unmarshal(buf)?;
+    let a2 = CsrcAudioLevelExtension {
+        levels: vec![0, 8, 127],
+    };
+    assert_eq!(a1, a2);
+
+    let mut dst = BytesMut::with_capacity(a2.marshal_size());
+    dst.resize(a2.marshal_size(), 0);
+    a2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_csrc_audio_level_extension_level_overflow() -> Result<()> {
+    let a = CsrcAudioLevelExtension {
+        levels: vec![1, 128],
+    };
+
+    let mut dst = BytesMut::with_capacity(a.marshal_size());
+    dst.resize(a.marshal_size(), 0);
+    let result = a.marshal_to(&mut dst);
+    assert_eq!(Error::AudioLevelOverflow, result.unwrap_err());
+
+    let a = CsrcAudioLevelExtension {
+        levels: vec![0; CSRC_AUDIO_LEVEL_MAX_COUNT + 1],
+    };
+    let mut dst = BytesMut::with_capacity(a.marshal_size());
+    dst.resize(a.marshal_size(), 0);
+    let result = a.marshal_to(&mut dst);
+    assert_eq!(Error::CsrcAudioLevelTooManyLevels, result.unwrap_err());
+
+    let mut raw = Bytes::from(vec![0u8; CSRC_AUDIO_LEVEL_MAX_COUNT + 1]);
+    let result = CsrcAudioLevelExtension::unmarshal(&mut raw);
+    assert_eq!(Error::CsrcAudioLevelTooManyLevels, result.unwrap_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_csrc_audio_level_extension_csrc_count() -> Result<()> {
+    let header = Header {
+        csrc: vec![0x1111, 0x2222],
+        ..Default::default()
+    };
+
+    let a = CsrcAudioLevelExtension {
+        levels: vec![10, 20],
+    };
+    assert_eq!(vec![(0x1111, 10), (0x2222, 20)], a.csrc_levels(&header)?);
+
+    let a = CsrcAudioLevelExtension { levels: vec![10] };
+    assert_eq!(
+        Err(Error::CsrcAudioLevelCountMismatch(1, 2)),
+        a.validate_csrc_count(&header)
+    );
+
+    Ok(())
+}
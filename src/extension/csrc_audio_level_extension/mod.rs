@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod csrc_audio_level_extension_test;
+
+use crate::{error::Error, header::Header};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use bytes::{Buf, BufMut};
+
+// CSRC_AUDIO_LEVEL_MAX_COUNT is the largest number of CSRCs an RTP header can carry
+pub const CSRC_AUDIO_LEVEL_MAX_COUNT: usize = 15;
+
+/// CsrcAudioLevelExtension is a extension payload format described in
+/// https://tools.ietf.org/html/rfc6465
+/// urn:ietf:params:rtp-hdrext:csrc-audio-level
+///
+/// Carries one audio level per CSRC, in the same order as the CSRC list of
+/// the RTP header. Levels are expressed in -dBov from 0 to 127.
+///
+/// One byte format:
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=2 |0|   level 1   |0|   level 2   |0|   level 3   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct CsrcAudioLevelExtension {
+    pub levels: Vec<u8>,
+}
+
+impl CsrcAudioLevelExtension {
+    /// validate_csrc_count checks that there is exactly one level per CSRC of the header
+    pub fn validate_csrc_count(&self, header: &Header) -> Result<(), Error> {
+        if self.levels.len() != header.csrc.len() {
+            return Err(Error::CsrcAudioLevelCountMismatch(
+                self.levels.len(),
+                header.csrc.len(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// csrc_levels pairs every CSRC of the header with its audio level
+    pub fn csrc_levels(&self, header: &Header) -> Result<Vec<(u32, u8)>, Error> {
+        self.validate_csrc_count(header)?;
+        Ok(header
+            .csrc
+            .iter()
+            .copied()
+            .zip(self.levels.iter().copied())
+            .collect())
+    }
+}
+
+impl Unmarshal for CsrcAudioLevelExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let count = raw_packet.remaining();
+        if count == 0 {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        if count > CSRC_AUDIO_LEVEL_MAX_COUNT {
+            return Err(Error::CsrcAudioLevelTooManyLevels.into());
+        }
+
+        let mut levels = Vec::with_capacity(count);
+        for _ in 0..count {
+            levels.push(raw_packet.get_u8() & 0x7F);
+        }
+
+        Ok(CsrcAudioLevelExtension { levels })
+    }
+}
+
+impl MarshalSize for CsrcAudioLevelExtension {
+    /// MarshalSize returns the size of the CsrcAudioLevelExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.levels.len()
+    }
+}
+
+impl Marshal for CsrcAudioLevelExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        if self.levels.len() > CSRC_AUDIO_LEVEL_MAX_COUNT {
+            return Err(Error::CsrcAudioLevelTooManyLevels.into());
+        }
+        if buf.remaining_mut() < self.levels.len() {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        for level in &self.levels {
+            if *level > 127 {
+                return Err(Error::AudioLevelOverflow.into());
+            }
+            buf.put_u8(*level);
+        }
+
+        Ok(self.levels.len())
+    }
+}
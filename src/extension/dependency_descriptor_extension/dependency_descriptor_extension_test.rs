@@ -0,0 +1,235 @@
+use super::*;
+use crate::error::Result;
+
+use DecodeTargetIndication::*;
+
+// L1T2: decode target 0 is T0, decode target 1 is T0+T1, both protected by one chain
+fn l1t2_structure() -> FrameDependencyStructure {
+    FrameDependencyStructure {
+        structure_id: 5,
+        num_decode_targets: 2,
+        num_chains: 1,
+        decode_target_protected_by_chain: vec![0, 0],
+        resolutions: vec![RenderResolution {
+            width: 640,
+            height: 360,
+        }],
+        templates: vec![
+            FrameDependencyTemplate {
+                spatial_id: 0,
+                temporal_id: 0,
+                decode_target_indications: vec![Switch, Switch],
+                frame_diffs: vec![],
+                chain_diffs: vec![0],
+            },
+            FrameDependencyTemplate {
+                spatial_id: 0,
+                temporal_id: 0,
+                decode_target_indications: vec![Switch, Switch],
+                frame_diffs: vec![2],
+                chain_diffs: vec![2],
+            },
+            FrameDependencyTemplate {
+                spatial_id: 0,
+                temporal_id: 1,
+                decode_target_indications: vec![NotPresent, Discardable],
+                frame_diffs: vec![1],
+                chain_diffs: vec![1],
+            },
+        ],
+    }
+}
+
+fn key_frame() -> DependencyDescriptor {
+    let structure = l1t2_structure();
+    DependencyDescriptor {
+        start_of_frame: true,
+        end_of_frame: true,
+        frame_number: 1,
+        frame_dependencies: structure.templates[0].clone(),
+        resolution: Some(structure.resolutions[0]),
+        active_decode_targets_bitmask: Some(0b11),
+        attached_structure: Some(structure),
+    }
+}
+
+#[test]
+fn test_dependency_descriptor_with_structure() -> Result<()> {
+    let mut writer = DependencyDescriptorContext::new();
+    let mut reader = DependencyDescriptorContext::new();
+
+    let key_frame = key_frame();
+    let raw = writer.marshal(&key_frame)?;
+    assert!(raw.len() > DEPENDENCY_DESCRIPTOR_MANDATORY_SIZE);
+
+    let parsed = reader.unmarshal(&raw)?;
+    assert_eq!(key_frame, parsed);
+    assert_eq!(Some(&l1t2_structure()), reader.structure());
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_structure_without_active_decode_targets() -> Result<()> {
+    let mut writer = DependencyDescriptorContext::new();
+    let mut reader = DependencyDescriptorContext::new();
+
+    let key_frame = DependencyDescriptor {
+        active_decode_targets_bitmask: None,
+        ..key_frame()
+    };
+    let raw = writer.marshal(&key_frame)?;
+    let parsed = reader.unmarshal(&raw)?;
+    assert_eq!(key_frame, parsed);
+    assert_eq!(None, parsed.active_decode_targets_bitmask);
+
+    // a bitmask with all decode targets is kept as well
+    let raw_with_bitmask = writer.marshal(&DependencyDescriptor {
+        active_decode_targets_bitmask: Some(0b11),
+        ..key_frame.clone()
+    })?;
+    assert_ne!(raw_with_bitmask, raw);
+    let parsed = reader.unmarshal(&raw_with_bitmask)?;
+    assert_eq!(Some(0b11), parsed.active_decode_targets_bitmask);
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_uses_previous_structure() -> Result<()> {
+    let mut writer = DependencyDescriptorContext::new();
+    let mut reader = DependencyDescriptorContext::new();
+    reader.unmarshal(&writer.marshal(&key_frame())?)?;
+
+    let structure = l1t2_structure();
+    let delta_frame = DependencyDescriptor {
+        start_of_frame: true,
+        end_of_frame: true,
+        frame_number: 2,
+        frame_dependencies: structure.templates[2].clone(),
+        resolution: Some(structure.resolutions[0]),
+        ..Default::default()
+    };
+
+    let raw = writer.marshal(&delta_frame)?;
+    // template index 2 with structure id 5 is template id 7
+    assert_eq!(&raw[..], &[0xC7, 0x00, 0x02]);
+
+    let parsed = reader.unmarshal(&raw)?;
+    assert_eq!(delta_frame, parsed);
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_custom_fields() -> Result<()> {
+    let mut writer = DependencyDescriptorContext::new();
+    let mut reader = DependencyDescriptorContext::new();
+    reader.unmarshal(&writer.marshal(&key_frame())?)?;
+
+    let frame = DependencyDescriptor {
+        start_of_frame: false,
+        end_of_frame: true,
+        frame_number: 0xFFFF,
+        frame_dependencies: FrameDependencyTemplate {
+            spatial_id: 0,
+            temporal_id: 0,
+            decode_target_indications: vec![Required, Switch],
+            frame_diffs: vec![1, 20, 300],
+            chain_diffs: vec![200],
+        },
+        resolution: Some(l1t2_structure().resolutions[0]),
+        active_decode_targets_bitmask: Some(0b01),
+        attached_structure: None,
+    };
+
+    let raw = writer.marshal(&frame)?;
+    let parsed = reader.unmarshal(&raw)?;
+    assert_eq!(frame, parsed);
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_missing_structure() -> Result<()> {
+    let mut reader = DependencyDescriptorContext::new();
+    let result = reader.unmarshal(&[0xC7, 0x00, 0x02]);
+    assert_eq!(Err(Error::DependencyDescriptorMissingStructure), result);
+
+    let mut writer = DependencyDescriptorContext::new();
+    let result = writer.marshal(&DependencyDescriptor::default());
+    assert_eq!(Err(Error::DependencyDescriptorMissingStructure), result);
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_invalid() -> Result<()> {
+    let mut writer = DependencyDescriptorContext::new();
+    let mut reader = DependencyDescriptorContext::new();
+    reader.unmarshal(&writer.marshal(&key_frame())?)?;
+
+    // template id 15 is template index 10, the structure has 3 templates
+    let result = reader.unmarshal(&[0xCF, 0x00, 0x02]);
+    assert_eq!(Err(Error::DependencyDescriptorInvalidTemplateId), result);
+
+    let result = reader.unmarshal(&[0xC7, 0x00]);
+    assert_eq!(Err(Error::ErrBufferTooSmall), result);
+
+    // no template for spatial layer 1
+    let mut frame = key_frame();
+    frame.attached_structure = None;
+    frame.frame_dependencies.spatial_id = 1;
+    let result = writer.marshal(&frame);
+    assert_eq!(
+        Err(Error::DependencyDescriptorInvalidFrameDependencies),
+        result
+    );
+
+    // templates are not ordered by layer
+    let mut frame = key_frame();
+    if let Some(structure) = &mut frame.attached_structure {
+        structure.templates.swap(0, 2);
+    }
+    let result = writer.marshal(&frame);
+    assert_eq!(Err(Error::DependencyDescriptorInvalidStructure), result);
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_decode_target_layers() -> Result<()> {
+    let layers = l1t2_structure().decode_target_layers();
+    assert_eq!(
+        vec![
+            DecodeTargetLayer {
+                spatial_id: 0,
+                temporal_id: 0
+            },
+            DecodeTargetLayer {
+                spatial_id: 0,
+                temporal_id: 1
+            },
+        ],
+        layers
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_non_symmetric() -> Result<()> {
+    for num_values in 1..=33u32 {
+        let mut writer = BitWriter::default();
+        for v in 0..num_values {
+            writer.write_non_symmetric(v, num_values);
+        }
+
+        let mut reader = BitReader::new(&writer.data);
+        for v in 0..num_values {
+            assert_eq!(v, reader.read_non_symmetric(num_values)?);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,669 @@
+#[cfg(test)]
+mod dependency_descriptor_extension_test;
+
+use crate::error::Error;
+
+use bytes::Bytes;
+
+// size of start_of_frame, end_of_frame, frame_dependency_template_id and frame_number
+pub const DEPENDENCY_DESCRIPTOR_MANDATORY_SIZE: usize = 3;
+pub const DEPENDENCY_DESCRIPTOR_MAX_TEMPLATES: usize = 64;
+pub const DEPENDENCY_DESCRIPTOR_MAX_DECODE_TARGETS: usize = 32;
+pub const DEPENDENCY_DESCRIPTOR_MAX_SPATIAL_IDS: usize = 4;
+pub const DEPENDENCY_DESCRIPTOR_MAX_TEMPORAL_IDS: usize = 8;
+
+const MAX_TEMPLATE_FRAME_DIFF: u16 = 16;
+const MAX_FRAME_DIFF: u16 = 1 << 12;
+const MAX_TEMPLATE_CHAIN_DIFF: u8 = 15;
+
+/// DecodeTargetIndication describes the relationship of a frame to a decode target
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeTargetIndication {
+    /// frame is not associated with the decode target
+    #[default]
+    NotPresent = 0,
+    /// frame is not needed to decode any later frame of the decode target
+    Discardable = 1,
+    /// frame is a switch point for the decode target
+    Switch = 2,
+    /// frame is needed to decode the decode target
+    Required = 3,
+}
+
+impl From<u8> for DecodeTargetIndication {
+    fn from(v: u8) -> Self {
+        match v & 0x3 {
+            1 => DecodeTargetIndication::Discardable,
+            2 => DecodeTargetIndication::Switch,
+            3 => DecodeTargetIndication::Required,
+            _ => DecodeTargetIndication::NotPresent,
+        }
+    }
+}
+
+/// RenderResolution is the maximum render size of frames in a spatial layer
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RenderResolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// DecodeTargetLayer is the highest spatial and temporal layer a decode target contains
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DecodeTargetLayer {
+    pub spatial_id: u8,
+    pub temporal_id: u8,
+}
+
+/// FrameDependencyTemplate describes the layer and the dependencies of a frame.
+/// It is used both for the templates of a structure and for the resolved
+/// dependencies of a single frame.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FrameDependencyTemplate {
+    pub spatial_id: u8,
+    pub temporal_id: u8,
+    /// one indication per decode target
+    pub decode_target_indications: Vec<DecodeTargetIndication>,
+    /// differences between the frame number of this frame and the frames it references
+    pub frame_diffs: Vec<u16>,
+    /// differences to the previous frame in each chain, 0 if this frame starts the chain
+    pub chain_diffs: Vec<u8>,
+}
+
+/// FrameDependencyStructure is the template dependency structure, sent
+/// on key frames and referenced by all following descriptors.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FrameDependencyStructure {
+    /// template_id_offset, the frame_dependency_template_id of the first template
+    pub structure_id: u8,
+    pub num_decode_targets: usize,
+    pub num_chains: usize,
+    /// index of the chain protecting each decode target
+    pub decode_target_protected_by_chain: Vec<usize>,
+    /// one resolution per spatial id, or empty if resolutions are not sent
+    pub resolutions: Vec<RenderResolution>,
+    pub templates: Vec<FrameDependencyTemplate>,
+}
+
+impl FrameDependencyStructure {
+    /// decode_target_layers returns the highest spatial and temporal id of the
+    /// frames that are part of each decode target
+    pub fn decode_target_layers(&self) -> Vec<DecodeTargetLayer> {
+        let mut layers = vec![DecodeTargetLayer::default(); self.num_decode_targets];
+        for template in &self.templates {
+            for (layer, dti) in layers
+                .iter_mut()
+                .zip(template.decode_target_indications.iter())
+            {
+                if *dti != DecodeTargetIndication::NotPresent {
+                    layer.spatial_id = layer.spatial_id.max(template.spatial_id);
+                    layer.temporal_id = layer.temporal_id.max(template.temporal_id);
+                }
+            }
+        }
+        layers
+    }
+
+    fn max_spatial_id(&self) -> u8 {
+        self.templates
+            .iter()
+            .map(|t| t.spatial_id)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.structure_id as usize >= DEPENDENCY_DESCRIPTOR_MAX_TEMPLATES
+            || self.num_decode_targets == 0
+            || self.num_decode_targets > DEPENDENCY_DESCRIPTOR_MAX_DECODE_TARGETS
+            || self.templates.is_empty()
+            || self.templates.len() > DEPENDENCY_DESCRIPTOR_MAX_TEMPLATES
+            || self.num_chains > self.num_decode_targets
+            || (self.num_chains > 0
+                && (self.decode_target_protected_by_chain.len() != self.num_decode_targets
+                    || self
+                        .decode_target_protected_by_chain
+                        .iter()
+                        .any(|c| *c >= self.num_chains)))
+        {
+            return Err(Error::DependencyDescriptorInvalidStructure);
+        }
+
+        let first = &self.templates[0];
+        if first.spatial_id != 0 || first.temporal_id != 0 {
+            return Err(Error::DependencyDescriptorInvalidStructure);
+        }
+        for pair in self.templates.windows(2) {
+            // templates must be sorted so template_layers can describe them
+            next_layer_idc(&pair[0], &pair[1])?;
+        }
+
+        for template in &self.templates {
+            if template.decode_target_indications.len() != self.num_decode_targets
+                || template.chain_diffs.len() != self.num_chains
+                || template
+                    .frame_diffs
+                    .iter()
+                    .any(|fdiff| *fdiff == 0 || *fdiff > MAX_TEMPLATE_FRAME_DIFF)
+                || template
+                    .chain_diffs
+                    .iter()
+                    .any(|cdiff| *cdiff > MAX_TEMPLATE_CHAIN_DIFF)
+            {
+                return Err(Error::DependencyDescriptorInvalidStructure);
+            }
+        }
+
+        if !self.resolutions.is_empty()
+            && (self.resolutions.len() != self.max_spatial_id() as usize + 1
+                || self.resolutions.iter().any(|r| {
+                    r.width == 0 || r.width > 1 << 16 || r.height == 0 || r.height > 1 << 16
+                }))
+        {
+            return Err(Error::DependencyDescriptorInvalidStructure);
+        }
+
+        Ok(())
+    }
+}
+
+/// DependencyDescriptor is the content of a single Dependency Descriptor
+/// header extension with its frame dependencies resolved against the
+/// template structure.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct DependencyDescriptor {
+    /// first packet of the frame
+    pub start_of_frame: bool,
+    /// last packet of the frame
+    pub end_of_frame: bool,
+    pub frame_number: u16,
+    pub frame_dependencies: FrameDependencyTemplate,
+    /// render resolution of the frame's spatial layer, if the structure carries resolutions
+    pub resolution: Option<RenderResolution>,
+    /// bitmask of the decode targets the sender is currently producing, None if
+    /// not signalled. A frame with an attached structure and no bitmask has all
+    /// decode targets active.
+    pub active_decode_targets_bitmask: Option<u32>,
+    /// template dependency structure, present on key frames
+    pub attached_structure: Option<FrameDependencyStructure>,
+}
+
+/// DependencyDescriptorContext parses and writes the Dependency Descriptor
+/// header extension described in
+/// https://aomediacodec.github.io/av1-rtp-spec/#dependency-descriptor-rtp-header-extension
+///
+/// The template dependency structure is only sent on key frames, so the
+/// context keeps the latest structure it has seen and resolves the
+/// descriptors of the following frames against it. Use one context per
+/// stream and per direction.
+///
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |S|E| template  |          frame number         | extended...   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(Default, Debug, Clone)]
+pub struct DependencyDescriptorContext {
+    structure: Option<FrameDependencyStructure>,
+}
+
+impl DependencyDescriptorContext {
+    pub fn new() -> Self {
+        DependencyDescriptorContext::default()
+    }
+
+    /// structure returns the latest template dependency structure
+    pub fn structure(&self) -> Option<&FrameDependencyStructure> {
+        self.structure.as_ref()
+    }
+
+    /// unmarshal parses a Dependency Descriptor, storing the attached
+    /// structure, if any, for the following descriptors
+    pub fn unmarshal(&mut self, raw: &[u8]) -> Result<DependencyDescriptor, Error> {
+        if raw.len() < DEPENDENCY_DESCRIPTOR_MANDATORY_SIZE {
+            return Err(Error::ErrBufferTooSmall);
+        }
+        let mut reader = BitReader::new(raw);
+
+        // mandatory_descriptor_fields
+        let start_of_frame = reader.read_bool()?;
+        let end_of_frame = reader.read_bool()?;
+        let template_id = reader.read_bits(6)? as usize;
+        let frame_number = reader.read_bits(16)? as u16;
+
+        let mut custom_dtis = false;
+        let mut custom_fdiffs = false;
+        let mut custom_chains = false;
+        let mut active_decode_targets_bitmask = None;
+        let mut attached_structure = None;
+
+        if raw.len() > DEPENDENCY_DESCRIPTOR_MANDATORY_SIZE {
+            // extended_descriptor_fields
+            let structure_present = reader.read_bool()?;
+            let active_decode_targets_present = reader.read_bool()?;
+            custom_dtis = reader.read_bool()?;
+            custom_fdiffs = reader.read_bool()?;
+            custom_chains = reader.read_bool()?;
+
+            if structure_present {
+                let structure = read_structure(&mut reader)?;
+                self.structure = Some(structure.clone());
+                attached_structure = Some(structure);
+            }
+
+            if active_decode_targets_present {
+                let structure = self
+                    .structure
+                    .as_ref()
+                    .ok_or(Error::DependencyDescriptorMissingStructure)?;
+                active_decode_targets_bitmask =
+                    Some(reader.read_bits(structure.num_decode_targets)?);
+            }
+        }
+
+        // frame_dependency_definition
+        let structure = self
+            .structure
+            .as_ref()
+            .ok_or(Error::DependencyDescriptorMissingStructure)?;
+        let template_index = (template_id + DEPENDENCY_DESCRIPTOR_MAX_TEMPLATES
+            - structure.structure_id as usize)
+            % DEPENDENCY_DESCRIPTOR_MAX_TEMPLATES;
+        let mut frame_dependencies = structure
+            .templates
+            .get(template_index)
+            .ok_or(Error::DependencyDescriptorInvalidTemplateId)?
+            .clone();
+
+        if custom_dtis {
+            for dti in frame_dependencies.decode_target_indications.iter_mut() {
+                *dti = DecodeTargetIndication::from(reader.read_bits(2)? as u8);
+            }
+        }
+
+        if custom_fdiffs {
+            frame_dependencies.frame_diffs.clear();
+            loop {
+                let next_fdiff_size = reader.read_bits(2)? as usize;
+                if next_fdiff_size == 0 {
+                    break;
+                }
+                let fdiff = reader.read_bits(4 * next_fdiff_size)? + 1;
+                frame_dependencies.frame_diffs.push(fdiff as u16);
+            }
+        }
+
+        if custom_chains {
+            for cdiff in frame_dependencies.chain_diffs.iter_mut() {
+                *cdiff = reader.read_bits(8)? as u8;
+            }
+        }
+
+        let resolution = structure
+            .resolutions
+            .get(frame_dependencies.spatial_id as usize)
+            .copied();
+
+        Ok(DependencyDescriptor {
+            start_of_frame,
+            end_of_frame,
+            frame_number,
+            frame_dependencies,
+            resolution,
+            active_decode_targets_bitmask,
+            attached_structure,
+        })
+    }
+
+    /// marshal serializes a Dependency Descriptor. If the descriptor carries a
+    /// structure, it replaces the one used for the following descriptors.
+    pub fn marshal(&mut self, descriptor: &DependencyDescriptor) -> Result<Bytes, Error> {
+        if let Some(structure) = &descriptor.attached_structure {
+            structure.validate()?;
+            self.structure = Some(structure.clone());
+        }
+        let structure = self
+            .structure
+            .as_ref()
+            .ok_or(Error::DependencyDescriptorMissingStructure)?;
+
+        let frame = &descriptor.frame_dependencies;
+        if frame.decode_target_indications.len() != structure.num_decode_targets
+            || frame.chain_diffs.len() != structure.num_chains
+            || frame
+                .frame_diffs
+                .iter()
+                .any(|fdiff| *fdiff == 0 || *fdiff > MAX_FRAME_DIFF)
+        {
+            return Err(Error::DependencyDescriptorInvalidFrameDependencies);
+        }
+
+        let (template_index, template) = find_best_template(structure, frame)?;
+        let custom_dtis = template.decode_target_indications != frame.decode_target_indications;
+        let custom_fdiffs = template.frame_diffs != frame.frame_diffs;
+        let custom_chains = template.chain_diffs != frame.chain_diffs;
+
+        let active_decode_targets_present = descriptor.active_decode_targets_bitmask.is_some();
+
+        let extended = descriptor.attached_structure.is_some()
+            || active_decode_targets_present
+            || custom_dtis
+            || custom_fdiffs
+            || custom_chains;
+
+        let mut writer = BitWriter::default();
+
+        // mandatory_descriptor_fields
+        writer.write_bool(descriptor.start_of_frame);
+        writer.write_bool(descriptor.end_of_frame);
+        let template_id = (template_index + structure.structure_id as usize)
+            % DEPENDENCY_DESCRIPTOR_MAX_TEMPLATES;
+        writer.write_bits(template_id as u64, 6);
+        writer.write_bits(descriptor.frame_number as u64, 16);
+
+        if extended {
+            // extended_descriptor_fields
+            writer.write_bool(descriptor.attached_structure.is_some());
+            writer.write_bool(active_decode_targets_present);
+            writer.write_bool(custom_dtis);
+            writer.write_bool(custom_fdiffs);
+            writer.write_bool(custom_chains);
+
+            if descriptor.attached_structure.is_some() {
+                write_structure(&mut writer, structure)?;
+            }
+
+            if let (true, Some(bitmask)) = (
+                active_decode_targets_present,
+                descriptor.active_decode_targets_bitmask,
+            ) {
+                writer.write_bits(bitmask as u64, structure.num_decode_targets);
+            }
+        }
+
+        // frame_dependency_definition
+        if custom_dtis {
+            for dti in &frame.decode_target_indications {
+                writer.write_bits(*dti as u64, 2);
+            }
+        }
+
+        if custom_fdiffs {
+            for fdiff in &frame.frame_diffs {
+                let fdiff_minus_one = (*fdiff - 1) as u64;
+                let next_fdiff_size = if fdiff_minus_one < 1 << 4 {
+                    1
+                } else if fdiff_minus_one < 1 << 8 {
+                    2
+                } else {
+                    3
+                };
+                writer.write_bits(next_fdiff_size as u64, 2);
+                writer.write_bits(fdiff_minus_one, 4 * next_fdiff_size);
+            }
+            writer.write_bits(0, 2);
+        }
+
+        if custom_chains {
+            for cdiff in &frame.chain_diffs {
+                writer.write_bits(*cdiff as u64, 8);
+            }
+        }
+
+        Ok(Bytes::from(writer.data))
+    }
+}
+
+/// find_best_template returns the template of the frame's layer that needs
+/// the fewest custom fields to describe the frame
+fn find_best_template<'a>(
+    structure: &'a FrameDependencyStructure,
+    frame: &FrameDependencyTemplate,
+) -> Result<(usize, &'a FrameDependencyTemplate), Error> {
+    structure
+        .templates
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.spatial_id == frame.spatial_id && t.temporal_id == frame.temporal_id)
+        .min_by_key(|(_, t)| {
+            (t.decode_target_indications != frame.decode_target_indications) as u8
+                + (t.frame_diffs != frame.frame_diffs) as u8
+                + (t.chain_diffs != frame.chain_diffs) as u8
+        })
+        .ok_or(Error::DependencyDescriptorInvalidFrameDependencies)
+}
+
+/// next_layer_idc returns how the layer of next is derived from the layer of prev
+fn next_layer_idc(
+    prev: &FrameDependencyTemplate,
+    next: &FrameDependencyTemplate,
+) -> Result<u64, Error> {
+    if next.spatial_id == prev.spatial_id && next.temporal_id == prev.temporal_id {
+        Ok(0)
+    } else if next.spatial_id == prev.spatial_id && next.temporal_id == prev.temporal_id + 1 {
+        Ok(1)
+    } else if next.spatial_id == prev.spatial_id + 1 && next.temporal_id == 0 {
+        Ok(2)
+    } else {
+        Err(Error::DependencyDescriptorInvalidStructure)
+    }
+}
+
+// template_dependency_structure
+fn read_structure(reader: &mut BitReader<'_>) -> Result<FrameDependencyStructure, Error> {
+    let structure_id = reader.read_bits(6)? as u8;
+    let num_decode_targets = reader.read_bits(5)? as usize + 1;
+
+    // template_layers
+    let mut templates = vec![];
+    let mut spatial_id = 0u8;
+    let mut temporal_id = 0u8;
+    loop {
+        if templates.len() == DEPENDENCY_DESCRIPTOR_MAX_TEMPLATES {
+            return Err(Error::DependencyDescriptorInvalidStructure);
+        }
+        templates.push(FrameDependencyTemplate {
+            spatial_id,
+            temporal_id,
+            ..Default::default()
+        });
+
+        match reader.read_bits(2)? {
+            0 => {}
+            1 => temporal_id += 1,
+            2 => {
+                temporal_id = 0;
+                spatial_id += 1;
+            }
+            _ => break,
+        }
+        if spatial_id as usize >= DEPENDENCY_DESCRIPTOR_MAX_SPATIAL_IDS
+            || temporal_id as usize >= DEPENDENCY_DESCRIPTOR_MAX_TEMPORAL_IDS
+        {
+            return Err(Error::DependencyDescriptorInvalidStructure);
+        }
+    }
+
+    // template_dtis
+    for template in templates.iter_mut() {
+        for _ in 0..num_decode_targets {
+            let dti = DecodeTargetIndication::from(reader.read_bits(2)? as u8);
+            template.decode_target_indications.push(dti);
+        }
+    }
+
+    // template_fdiffs
+    for template in templates.iter_mut() {
+        while reader.read_bool()? {
+            let fdiff = reader.read_bits(4)? + 1;
+            template.frame_diffs.push(fdiff as u16);
+        }
+    }
+
+    // template_chains
+    let num_chains = reader.read_non_symmetric(num_decode_targets as u32 + 1)? as usize;
+    let mut decode_target_protected_by_chain = vec![];
+    if num_chains > 0 {
+        for _ in 0..num_decode_targets {
+            let chain = reader.read_non_symmetric(num_chains as u32)? as usize;
+            decode_target_protected_by_chain.push(chain);
+        }
+        for template in templates.iter_mut() {
+            for _ in 0..num_chains {
+                template.chain_diffs.push(reader.read_bits(4)? as u8);
+            }
+        }
+    }
+
+    // render_resolutions
+    let mut resolutions = vec![];
+    if reader.read_bool()? {
+        for _ in 0..=spatial_id {
+            let width = reader.read_bits(16)? + 1;
+            let height = reader.read_bits(16)? + 1;
+            resolutions.push(RenderResolution { width, height });
+        }
+    }
+
+    Ok(FrameDependencyStructure {
+        structure_id,
+        num_decode_targets,
+        num_chains,
+        decode_target_protected_by_chain,
+        resolutions,
+        templates,
+    })
+}
+
+// template_dependency_structure
+fn write_structure(
+    writer: &mut BitWriter,
+    structure: &FrameDependencyStructure,
+) -> Result<(), Error> {
+    writer.write_bits(structure.structure_id as u64, 6);
+    writer.write_bits(structure.num_decode_targets as u64 - 1, 5);
+
+    // template_layers
+    for pair in structure.templates.windows(2) {
+        writer.write_bits(next_layer_idc(&pair[0], &pair[1])?, 2);
+    }
+    writer.write_bits(3, 2);
+
+    // template_dtis
+    for template in &structure.templates {
+        for dti in &template.decode_target_indications {
+            writer.write_bits(*dti as u64, 2);
+        }
+    }
+
+    // template_fdiffs
+    for template in &structure.templates {
+        for fdiff in &template.frame_diffs {
+            writer.write_bool(true);
+            writer.write_bits(*fdiff as u64 - 1, 4);
+        }
+        writer.write_bool(false);
+    }
+
+    // template_chains
+    writer.write_non_symmetric(
+        structure.num_chains as u32,
+        structure.num_decode_targets as u32 + 1,
+    );
+    if structure.num_chains > 0 {
+        for chain in &structure.decode_target_protected_by_chain {
+            writer.write_non_symmetric(*chain as u32, structure.num_chains as u32);
+        }
+        for template in &structure.templates {
+            for cdiff in &template.chain_diffs {
+                writer.write_bits(*cdiff as u64, 4);
+            }
+        }
+    }
+
+    // render_resolutions
+    writer.write_bool(!structure.resolutions.is_empty());
+    for resolution in &structure.resolutions {
+        writer.write_bits(resolution.width as u64 - 1, 16);
+        writer.write_bits(resolution.height as u64 - 1, 16);
+    }
+
+    Ok(())
+}
+
+/// BitReader reads MSB first bit fields
+struct BitReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader { data, offset: 0 }
+    }
+
+    fn read_bits(&mut self, n: usize) -> Result<u32, Error> {
+        if self.offset + n > self.data.len() * 8 {
+            return Err(Error::ErrBufferTooSmall);
+        }
+        let mut v = 0u32;
+        for _ in 0..n {
+            let bit = (self.data[self.offset / 8] >> (7 - self.offset % 8)) & 0x1;
+            v = (v << 1) | bit as u32;
+            self.offset += 1;
+        }
+        Ok(v)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, Error> {
+        Ok(self.read_bits(1)? == 1)
+    }
+
+    /// read_non_symmetric reads a value in [0, num_values) coded with ns(n)
+    fn read_non_symmetric(&mut self, num_values: u32) -> Result<u32, Error> {
+        let w = 32 - num_values.leading_zeros() as usize;
+        let m = (1u32 << w) - num_values;
+        let v = self.read_bits(w - 1)?;
+        if v < m {
+            return Ok(v);
+        }
+        let extra_bit = self.read_bits(1)?;
+        Ok((v << 1) - m + extra_bit)
+    }
+}
+
+/// BitWriter writes MSB first bit fields, padding the last byte with zeros
+#[derive(Default)]
+struct BitWriter {
+    data: Vec<u8>,
+    offset: usize,
+}
+
+impl BitWriter {
+    fn write_bits(&mut self, v: u64, n: usize) {
+        for i in (0..n).rev() {
+            if self.offset / 8 == self.data.len() {
+                self.data.push(0);
+            }
+            if (v >> i) & 0x1 == 1 {
+                self.data[self.offset / 8] |= 0x80 >> (self.offset % 8);
+            }
+            self.offset += 1;
+        }
+    }
+
+    fn write_bool(&mut self, v: bool) {
+        self.write_bits(v as u64, 1);
+    }
+
+    /// write_non_symmetric writes a value in [0, num_values) coded with ns(n)
+    fn write_non_symmetric(&mut self, v: u32, num_values: u32) {
+        let w = 32 - num_values.leading_zeros() as usize;
+        let m = (1u32 << w) - num_values;
+        if v < m {
+            self.write_bits(v as u64, w - 1);
+        } else {
+            self.write_bits((v + m) as u64, w);
+        }
+    }
+}
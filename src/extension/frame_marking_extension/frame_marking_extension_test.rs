@@ -0,0 +1,153 @@
+use super::*;
+use crate::error::Result;
+use crate::packetizer::Depacketizer;
+use bytes::BytesMut;
+
+#[test]
+fn test_frame_marking_extension_too_small() -> Result<()> {
+    for len in [0, 2] {
+        let mut buf = &vec![0u8; len][..];
+        let result = FrameMarkingExtension::unmarshal(&mut buf);
+        assert!(result.is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_marking_extension_short() -> Result<()> {
+    let raw = Bytes::from_static(&[0xa0]);
+    let buf = &mut raw.clone();
+    let f1 = FrameMarkingExtension::unmarshal(buf)?;
+    let f2 = FrameMarkingExtension {
+        start_of_frame: true,
+        end_of_frame: false,
+        independent: true,
+        discardable: false,
+        layer: None,
+    };
+    assert_eq!(f1, f2);
+
+    let mut dst = BytesMut::with_capacity(f2.marshal_size());
+    dst.resize(f2.marshal_size(), 0);
+    f2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_marking_extension_long() -> Result<()> {
+    let raw = Bytes::from_static(&[0x5a, 0x01, 0x7f]);
+    let buf = &mut raw.clone();
+    let f1 = FrameMarkingExtension::unmarshal(buf)?;
+    let f2 = FrameMarkingExtension {
+        start_of_frame: false,
+        end_of_frame: true,
+        independent: false,
+        discardable: true,
+        layer: Some(FrameMarkingLayer {
+            base_layer_sync: true,
+            tid: 2,
+            lid: 1,
+            tl0_pic_idx: 0x7f,
+        }),
+    };
+    assert_eq!(f1, f2);
+
+    let mut dst = BytesMut::with_capacity(f2.marshal_size());
+    dst.resize(f2.marshal_size(), 0);
+    f2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_marking_extension_tid_overflow() -> Result<()> {
+    let f = FrameMarkingExtension {
+        layer: Some(FrameMarkingLayer {
+            tid: 8,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut dst = BytesMut::with_capacity(f.marshal_size());
+    dst.resize(f.marshal_size(), 0);
+    let result = f.marshal_to(&mut dst);
+    assert_eq!(Error::FrameMarkingTidOverflow, result.unwrap_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_marking_extension_from_vp8() -> Result<()> {
+    // X=1 S=1, L=1 T=1, tl0picidx=5, tid=1 Y=1, key frame
+    let raw = Bytes::from_static(&[0x90, 0x60, 0x05, 0x60, 0x00, 0x01, 0x02]);
+    let mut vp8 = Vp8Packet::default();
+    let payload = vp8.depacketize(&raw)?;
+
+    let f = FrameMarkingExtension::from_vp8(&vp8, &payload, false);
+    assert_eq!(
+        FrameMarkingExtension {
+            start_of_frame: true,
+            end_of_frame: false,
+            independent: true,
+            discardable: false,
+            layer: Some(FrameMarkingLayer {
+                base_layer_sync: true,
+                tid: 1,
+                lid: 0,
+                tl0_pic_idx: 5,
+            }),
+        },
+        f
+    );
+
+    // N=1, continuation of a delta frame without layer information
+    let raw = Bytes::from_static(&[0x20, 0x01, 0x02, 0x03]);
+    let mut vp8 = Vp8Packet::default();
+    let payload = vp8.depacketize(&raw)?;
+
+    let f = FrameMarkingExtension::from_vp8(&vp8, &payload, true);
+    assert_eq!(
+        FrameMarkingExtension {
+            start_of_frame: false,
+            end_of_frame: true,
+            independent: false,
+            discardable: true,
+            layer: None,
+        },
+        f
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_marking_extension_from_vp9() -> Result<()> {
+    // I=0 P=0 L=1 F=0 B=1 E=1, tid=0 U=1 sid=1 D=0, tl0picidx=3
+    let raw = Bytes::from_static(&[0x2c, 0x12, 0x03, 0xaa]);
+    let mut vp9 = Vp9Packet::default();
+    vp9.depacketize(&raw)?;
+
+    let f = FrameMarkingExtension::from_vp9(&vp9);
+    assert_eq!(
+        FrameMarkingExtension {
+            start_of_frame: true,
+            end_of_frame: true,
+            independent: true,
+            discardable: false,
+            layer: Some(FrameMarkingLayer {
+                base_layer_sync: true,
+                tid: 0,
+                lid: 1,
+                tl0_pic_idx: 3,
+            }),
+        },
+        f
+    );
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod frame_marking_extension_test;
+
+use crate::{
+    codecs::{vp8::Vp8Packet, vp9::Vp9Packet},
+    error::Error,
+};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use bytes::{Buf, BufMut, Bytes};
+
+// short form, for non-scalable streams
+pub const FRAME_MARKING_EXTENSION_SHORT_SIZE: usize = 1;
+// long form, for scalable streams
+pub const FRAME_MARKING_EXTENSION_LONG_SIZE: usize = 3;
+
+/// FrameMarkingLayer is the layer information of the long form
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct FrameMarkingLayer {
+    /// B: the frame only depends on the base temporal layer
+    pub base_layer_sync: bool,
+    /// 3 bits temporal layer id
+    pub tid: u8,
+    /// layer id, the spatial or quality layer
+    pub lid: u8,
+    /// temporal level zero index
+    pub tl0_pic_idx: u8,
+}
+
+/// FrameMarkingExtension is a extension payload format described in
+/// https://tools.ietf.org/html/draft-ietf-avtext-framemarking-13
+/// urn:ietf:params:rtp-hdrext:framemarking
+///
+/// Short form:
+/// 0                   1
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | L=0   |S|E|I|D|0 0 0 0|
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///
+/// Long form:
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | L=2   |S|E|I|D|B| TID |      LID      |   TL0PICIDX   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct FrameMarkingExtension {
+    /// S: first packet of the frame
+    pub start_of_frame: bool,
+    /// E: last packet of the frame
+    pub end_of_frame: bool,
+    /// I: the frame can be decoded without any previous frame
+    pub independent: bool,
+    /// D: the frame is not used as a reference by any other frame
+    pub discardable: bool,
+    /// layer information, the short form is used when it is None
+    pub layer: Option<FrameMarkingLayer>,
+}
+
+impl FrameMarkingExtension {
+    /// from_vp8 derives the frame marking of a packet from its VP8 payload
+    /// descriptor, the VP8 payload returned by depacketize and the RTP marker bit.
+    /// Key frames can only be detected on the first packet of a frame, so
+    /// `independent` is false on the following packets.
+    pub fn from_vp8(vp8: &Vp8Packet, payload: &Bytes, marker: bool) -> Self {
+        let start_of_frame = vp8.s == 1 && vp8.pid == 0;
+        // the P bit of the VP8 frame tag is 0 for key frames
+        let independent = start_of_frame && !payload.is_empty() && payload[0] & 0x01 == 0;
+        let layer = if vp8.t == 1 || vp8.l == 1 {
+            Some(FrameMarkingLayer {
+                base_layer_sync: vp8.y == 1,
+                tid: vp8.tid,
+                lid: 0,
+                tl0_pic_idx: vp8.tl0_pic_idx,
+            })
+        } else {
+            None
+        };
+
+        FrameMarkingExtension {
+            start_of_frame,
+            end_of_frame: marker,
+            independent,
+            discardable: vp8.n == 1,
+            layer,
+        }
+    }
+
+    /// from_vp9 derives the frame marking of a packet from its VP9 payload
+    /// descriptor. VP9 does not signal whether a frame is discardable, so
+    /// `discardable` is always false.
+    pub fn from_vp9(vp9: &Vp9Packet) -> Self {
+        let layer = if vp9.l {
+            Some(FrameMarkingLayer {
+                base_layer_sync: vp9.u,
+                tid: vp9.tid,
+                lid: vp9.sid,
+                tl0_pic_idx: vp9.tl0picidx,
+            })
+        } else {
+            None
+        };
+
+        FrameMarkingExtension {
+            start_of_frame: vp9.b,
+            end_of_frame: vp9.e,
+            independent: !vp9.p && !vp9.d,
+            discardable: false,
+            layer,
+        }
+    }
+}
+
+impl Unmarshal for FrameMarkingExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let len = raw_packet.remaining();
+        if len != FRAME_MARKING_EXTENSION_SHORT_SIZE && len < FRAME_MARKING_EXTENSION_LONG_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let b0 = raw_packet.get_u8();
+        let layer = if len >= FRAME_MARKING_EXTENSION_LONG_SIZE {
+            let lid = raw_packet.get_u8();
+            let tl0_pic_idx = raw_packet.get_u8();
+            Some(FrameMarkingLayer {
+                base_layer_sync: (b0 & 0x08) != 0,
+                tid: b0 & 0x07,
+                lid,
+                tl0_pic_idx,
+            })
+        } else {
+            None
+        };
+
+        Ok(FrameMarkingExtension {
+            start_of_frame: (b0 & 0x80) != 0,
+            end_of_frame: (b0 & 0x40) != 0,
+            independent: (b0 & 0x20) != 0,
+            discardable: (b0 & 0x10) != 0,
+            layer,
+        })
+    }
+}
+
+impl MarshalSize for FrameMarkingExtension {
+    /// MarshalSize returns the size of the FrameMarkingExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        if self.layer.is_some() {
+            FRAME_MARKING_EXTENSION_LONG_SIZE
+        } else {
+            FRAME_MARKING_EXTENSION_SHORT_SIZE
+        }
+    }
+}
+
+impl Marshal for FrameMarkingExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        let size = self.marshal_size();
+        if buf.remaining_mut() < size {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let mut b0 = 0u8;
+        if self.start_of_frame {
+            b0 |= 0x80;
+        }
+        if self.end_of_frame {
+            b0 |= 0x40;
+        }
+        if self.independent {
+            b0 |= 0x20;
+        }
+        if self.discardable {
+            b0 |= 0x10;
+        }
+
+        if let Some(layer) = &self.layer {
+            if layer.tid > 7 {
+                return Err(Error::FrameMarkingTidOverflow.into());
+            }
+            if layer.base_layer_sync {
+                b0 |= 0x08;
+            }
+            buf.put_u8(b0 | layer.tid);
+            buf.put_u8(layer.lid);
+            buf.put_u8(layer.tl0_pic_idx);
+        } else {
+            buf.put_u8(b0);
+        }
+
+        Ok(size)
+    }
+}
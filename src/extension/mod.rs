@@ -1,3 +1,12 @@
 pub mod abs_send_time_extension;
 pub mod audio_level_extension;
+pub mod color_space_extension;
+pub mod csrc_audio_level_extension;
+pub mod dependency_descriptor_extension;
+pub mod frame_marking_extension;
+pub mod sdes_extension;
+pub mod transmission_offset_extension;
 pub mod transport_cc_extension;
+pub mod video_content_type_extension;
+pub mod video_layers_allocation_extension;
+pub mod video_timing_extension;
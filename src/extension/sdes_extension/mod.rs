@@ -0,0 +1,231 @@
+#[cfg(test)]
+mod sdes_extension_test;
+
+use crate::error::Error;
+use crate::header::{EXTENSION_PROFILE_ONE_BYTE, EXTENSION_PROFILE_TWO_BYTE};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use bytes::{Buf, BufMut};
+
+// SDES_ITEM_MAX_SIZE is the largest value a two byte header extension can carry
+pub const SDES_ITEM_MAX_SIZE: usize = 255;
+// SDES_ITEM_ONE_BYTE_MAX_SIZE is the largest value a one byte header extension can carry
+pub const SDES_ITEM_ONE_BYTE_MAX_SIZE: usize = 16;
+
+/// MidExtension is a extension payload format described in
+/// https://tools.ietf.org/html/rfc8843#section-15.1
+/// urn:ietf:params:rtp-hdrext:sdes:mid
+///
+/// The value is the media identification tag of the m= section, a `token`
+/// as defined in https://tools.ietf.org/html/rfc4566#section-9
+///
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   |  len  | identification-tag (1-16 bytes) ...           |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct MidExtension {
+    pub mid: String,
+}
+
+/// RtpStreamIdExtension is a extension payload format described in
+/// https://tools.ietf.org/html/rfc8852#section-3.1
+/// urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id
+///
+/// The value is a `rid-id` as defined in https://tools.ietf.org/html/rfc8851#section-10:
+/// alpha-numeric characters, "-" and "_".
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct RtpStreamIdExtension {
+    pub rid: String,
+}
+
+/// RepairedRtpStreamIdExtension is a extension payload format described in
+/// https://tools.ietf.org/html/rfc8852#section-3.2
+/// urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id
+///
+/// It carries the RtpStreamId of the stream a redundancy or retransmission
+/// stream repairs, with the same syntax as RtpStreamIdExtension.
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct RepairedRtpStreamIdExtension {
+    pub rid: String,
+}
+
+/// is_token_char reports whether b is a `token-char` of RFC 4566
+fn is_token_char(b: u8) -> bool {
+    matches!(
+        b,
+        0x21 | 0x23..=0x27 | 0x2A..=0x2B | 0x2D..=0x2E | 0x30..=0x39 | 0x41..=0x5A | 0x5E..=0x7E
+    )
+}
+
+/// is_rid_char reports whether b may appear in a `rid-id` of RFC 8851
+fn is_rid_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'-' || b == b'_'
+}
+
+/// validate_sdes_item_size checks that an SDES item value of size bytes fits a
+/// header extension of the given profile: 1 to 16 bytes for the one byte
+/// profile, 1 to 255 bytes otherwise
+pub fn validate_sdes_item_size(size: usize, extension_profile: u16) -> Result<(), Error> {
+    let max_size = if extension_profile == EXTENSION_PROFILE_ONE_BYTE {
+        SDES_ITEM_ONE_BYTE_MAX_SIZE
+    } else {
+        SDES_ITEM_MAX_SIZE
+    };
+    if size == 0 || size > max_size {
+        return Err(Error::SdesItemSizeInvalid);
+    }
+    Ok(())
+}
+
+/// validate_sdes_item checks the length and character set of an SDES item
+/// value. The length is checked against the two byte profile, the profile
+/// of a header is checked by the Header getters.
+fn validate_sdes_item(value: &[u8], is_valid_char: fn(u8) -> bool) -> Result<(), Error> {
+    validate_sdes_item_size(value.len(), EXTENSION_PROFILE_TWO_BYTE)?;
+    if let Some(b) = value.iter().find(|b| !is_valid_char(**b)) {
+        return Err(Error::SdesItemInvalidCharacter(*b));
+    }
+    Ok(())
+}
+
+/// unmarshal_sdes_item reads the whole remaining buffer as an SDES item value.
+/// Trailing zero bytes are ignored, some senders null-terminate the value.
+fn unmarshal_sdes_item<B>(
+    raw_packet: &mut B,
+    is_valid_char: fn(u8) -> bool,
+) -> Result<String, Error>
+where
+    B: Buf,
+{
+    let raw = raw_packet.copy_to_bytes(raw_packet.remaining());
+    let len = raw.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    validate_sdes_item(&raw[..len], is_valid_char)?;
+
+    // all valid characters are ASCII
+    Ok(String::from_utf8_lossy(&raw[..len]).into_owned())
+}
+
+/// marshal_sdes_item validates and writes an SDES item value to buf
+fn marshal_sdes_item(
+    value: &str,
+    is_valid_char: fn(u8) -> bool,
+    mut buf: &mut [u8],
+) -> Result<usize, Error> {
+    validate_sdes_item(value.as_bytes(), is_valid_char)?;
+    if buf.remaining_mut() < value.len() {
+        return Err(Error::ErrBufferTooSmall);
+    }
+    buf.put(value.as_bytes());
+
+    Ok(value.len())
+}
+
+impl MidExtension {
+    /// new creates a MidExtension, returning an error if mid is not a valid token
+    pub fn new(mid: &str) -> Result<Self, Error> {
+        validate_sdes_item(mid.as_bytes(), is_token_char)?;
+        Ok(MidExtension {
+            mid: mid.to_owned(),
+        })
+    }
+}
+
+impl Unmarshal for MidExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let mid = unmarshal_sdes_item(raw_packet, is_token_char)?;
+        Ok(MidExtension { mid })
+    }
+}
+
+impl MarshalSize for MidExtension {
+    /// MarshalSize returns the size of the MidExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.mid.len()
+    }
+}
+
+impl Marshal for MidExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize, util::Error> {
+        Ok(marshal_sdes_item(&self.mid, is_token_char, buf)?)
+    }
+}
+
+impl RtpStreamIdExtension {
+    /// new creates a RtpStreamIdExtension, returning an error if rid is not a valid rid-id
+    pub fn new(rid: &str) -> Result<Self, Error> {
+        validate_sdes_item(rid.as_bytes(), is_rid_char)?;
+        Ok(RtpStreamIdExtension {
+            rid: rid.to_owned(),
+        })
+    }
+}
+
+impl Unmarshal for RtpStreamIdExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let rid = unmarshal_sdes_item(raw_packet, is_rid_char)?;
+        Ok(RtpStreamIdExtension { rid })
+    }
+}
+
+impl MarshalSize for RtpStreamIdExtension {
+    /// MarshalSize returns the size of the RtpStreamIdExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.rid.len()
+    }
+}
+
+impl Marshal for RtpStreamIdExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize, util::Error> {
+        Ok(marshal_sdes_item(&self.rid, is_rid_char, buf)?)
+    }
+}
+
+impl RepairedRtpStreamIdExtension {
+    /// new creates a RepairedRtpStreamIdExtension, returning an error if rid is not a valid rid-id
+    pub fn new(rid: &str) -> Result<Self, Error> {
+        validate_sdes_item(rid.as_bytes(), is_rid_char)?;
+        Ok(RepairedRtpStreamIdExtension {
+            rid: rid.to_owned(),
+        })
+    }
+}
+
+impl Unmarshal for RepairedRtpStreamIdExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let rid = unmarshal_sdes_item(raw_packet, is_rid_char)?;
+        Ok(RepairedRtpStreamIdExtension { rid })
+    }
+}
+
+impl MarshalSize for RepairedRtpStreamIdExtension {
+    /// MarshalSize returns the size of the RepairedRtpStreamIdExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.rid.len()
+    }
+}
+
+impl Marshal for RepairedRtpStreamIdExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize, util::Error> {
+        Ok(marshal_sdes_item(&self.rid, is_rid_char, buf)?)
+    }
+}
@@ -0,0 +1,149 @@
+use super::*;
+use crate::error::Result;
+use crate::header::{Extension, Header, EXTENSION_PROFILE_ONE_BYTE};
+use bytes::{Bytes, BytesMut};
+
+#[test]
+fn test_mid_extension() -> Result<()> {
+    let raw = Bytes::from_static(b"audio-0");
+    let buf = &mut raw.clone();
+    let m1 = MidExtension::unmarshal(buf)?;
+    let m2 = MidExtension::new("audio-0")?;
+    assert_eq!(m1, m2);
+
+    let mut dst = BytesMut::with_capacity(m2.marshal_size());
+    dst.resize(m2.marshal_size(), 0);
+    m2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_mid_extension_null_terminated() -> Result<()> {
+    let mut raw = Bytes::from_static(b"0\x00\x00");
+    let m = MidExtension::unmarshal(&mut raw)?;
+    assert_eq!(m.mid, "0");
+
+    Ok(())
+}
+
+#[test]
+fn test_rtp_stream_id_extension() -> Result<()> {
+    let raw = Bytes::from_static(b"hi_res-1");
+    let buf = &mut raw.clone();
+    let r1 = RtpStreamIdExtension::unmarshal(buf)?;
+    let r2 = RtpStreamIdExtension::new("hi_res-1")?;
+    assert_eq!(r1, r2);
+
+    let mut dst = BytesMut::with_capacity(r2.marshal_size());
+    dst.resize(r2.marshal_size(), 0);
+    r2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    let mut raw = Bytes::from_static(b"lo");
+    let r = RepairedRtpStreamIdExtension::unmarshal(&mut raw)?;
+    assert_eq!(r.rid, "lo");
+
+    Ok(())
+}
+
+#[test]
+fn test_sdes_extension_invalid() -> Result<()> {
+    let mut empty = Bytes::new();
+    let result = MidExtension::unmarshal(&mut empty);
+    assert_eq!(Error::SdesItemSizeInvalid, result.unwrap_err());
+
+    let mut zeros = Bytes::from_static(&[0, 0]);
+    let result = RtpStreamIdExtension::unmarshal(&mut zeros);
+    assert_eq!(Error::SdesItemSizeInvalid, result.unwrap_err());
+
+    let mut raw = Bytes::from_static(b"a b");
+    let result = MidExtension::unmarshal(&mut raw);
+    assert_eq!(Error::SdesItemInvalidCharacter(b' '), result.unwrap_err());
+
+    // "." is a valid token-char but not a valid rid-id character
+    assert!(MidExtension::new("v.1").is_ok());
+    assert_eq!(
+        Err(Error::SdesItemInvalidCharacter(b'.')),
+        RtpStreamIdExtension::new("v.1")
+    );
+
+    let too_long = "a".repeat(SDES_ITEM_MAX_SIZE + 1);
+    assert_eq!(
+        Err(Error::SdesItemSizeInvalid),
+        RepairedRtpStreamIdExtension::new(&too_long)
+    );
+
+    let r = RtpStreamIdExtension {
+        rid: "bad rid".to_owned(),
+    };
+    let mut dst = BytesMut::with_capacity(r.marshal_size());
+    dst.resize(r.marshal_size(), 0);
+    assert!(r.marshal_to(&mut dst).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_header_get_sdes_extensions() -> Result<()> {
+    let mut header = Header::default();
+    header.set_extension(1, Bytes::from_static(b"1"))?;
+    header.set_extension(2, Bytes::from_static(b"h"))?;
+    header.set_extension(3, Bytes::from_static(b"f"))?;
+
+    assert_eq!(Some(MidExtension::new("1")?), header.get_mid(1)?);
+    assert_eq!(Some(RtpStreamIdExtension::new("h")?), header.get_rid(2)?);
+    assert_eq!(
+        Some(RepairedRtpStreamIdExtension::new("f")?),
+        header.get_repaired_rid(3)?
+    );
+    assert_eq!(None, header.get_mid(4)?);
+
+    header.set_extension(4, Bytes::from_static(b"a b"))?;
+    assert!(header.get_rid(4).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_sdes_item_size_per_profile() -> Result<()> {
+    for (size, one_byte_ok, two_byte_ok) in [
+        (0, false, false),
+        (1, true, true),
+        (SDES_ITEM_ONE_BYTE_MAX_SIZE, true, true),
+        (SDES_ITEM_ONE_BYTE_MAX_SIZE + 1, false, true),
+        (SDES_ITEM_MAX_SIZE, false, true),
+        (SDES_ITEM_MAX_SIZE + 1, false, false),
+    ] {
+        assert_eq!(
+            validate_sdes_item_size(size, EXTENSION_PROFILE_ONE_BYTE).is_ok(),
+            one_byte_ok
+        );
+        assert_eq!(
+            validate_sdes_item_size(size, EXTENSION_PROFILE_TWO_BYTE).is_ok(),
+            two_byte_ok
+        );
+    }
+
+    // a 17 bytes RID only fits the two byte profile
+    let rid = Bytes::from(vec![b'a'; SDES_ITEM_ONE_BYTE_MAX_SIZE + 1]);
+    let mut header = Header {
+        extension: true,
+        extension_profile: EXTENSION_PROFILE_TWO_BYTE,
+        extensions: vec![Extension {
+            id: 2,
+            payload: rid.clone(),
+        }],
+        ..Default::default()
+    };
+    assert_eq!(header.get_rid(2)?.map(|r| r.rid.len()), Some(rid.len()));
+
+    header.extension_profile = EXTENSION_PROFILE_ONE_BYTE;
+    match header.get_rid(2) {
+        Err(err) => assert_eq!(Error::SdesItemSizeInvalid, err),
+        Ok(_) => panic!("expected an invalid size"),
+    }
+
+    Ok(())
+}
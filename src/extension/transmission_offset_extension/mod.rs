@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod transmission_offset_extension_test;
+
+use crate::error::Error;
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use bytes::{Buf, BufMut};
+
+pub const TRANSMISSION_OFFSET_EXTENSION_SIZE: usize = 3;
+// largest and smallest values of a signed 24-bit integer
+pub const TRANSMISSION_OFFSET_MAX: i32 = 0x7FFFFF;
+pub const TRANSMISSION_OFFSET_MIN: i32 = -0x800000;
+
+/// TransmissionOffsetExtension is a extension payload format described in
+/// https://tools.ietf.org/html/rfc5450
+/// urn:ietf:params:rtp-hdrext:toffset
+///
+/// The offset is the difference between the transmission time of the packet
+/// and its RTP timestamp, in RTP clock units, as a signed 24-bit integer.
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=2 |              transmission offset              |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct TransmissionOffsetExtension {
+    pub offset: i32,
+}
+
+impl Unmarshal for TransmissionOffsetExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < TRANSMISSION_OFFSET_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let b0 = raw_packet.get_u8();
+        let b1 = raw_packet.get_u8();
+        let b2 = raw_packet.get_u8();
+        // shift into the top 24 bits and back to sign-extend
+        let offset = ((b0 as i32) << 24 | (b1 as i32) << 16 | (b2 as i32) << 8) >> 8;
+
+        Ok(TransmissionOffsetExtension { offset })
+    }
+}
+
+impl MarshalSize for TransmissionOffsetExtension {
+    /// MarshalSize returns the size of the TransmissionOffsetExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        TRANSMISSION_OFFSET_EXTENSION_SIZE
+    }
+}
+
+impl Marshal for TransmissionOffsetExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        if buf.remaining_mut() < TRANSMISSION_OFFSET_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        if !(TRANSMISSION_OFFSET_MIN..=TRANSMISSION_OFFSET_MAX).contains(&self.offset) {
+            return Err(Error::TransmissionOffsetOverflow.into());
+        }
+
+        buf.put_u8((self.offset >> 16) as u8);
+        buf.put_u8((self.offset >> 8) as u8);
+        buf.put_u8(self.offset as u8);
+
+        Ok(TRANSMISSION_OFFSET_EXTENSION_SIZE)
+    }
+}
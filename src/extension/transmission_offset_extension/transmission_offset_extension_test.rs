@@ -0,0 +1,58 @@
+use super::*;
+use crate::error::Result;
+use bytes::{Bytes, BytesMut};
+
+#[test]
+fn test_transmission_offset_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 2][..];
+    let result = TransmissionOffsetExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_transmission_offset_extension() -> Result<()> {
+    let tests = vec![
+        (Bytes::from_static(&[0x00, 0x00, 0x00]), 0),
+        (Bytes::from_static(&[0x00, 0x01, 0x00]), 256),
+        (
+            Bytes::from_static(&[0x7f, 0xff, 0xff]),
+            TRANSMISSION_OFFSET_MAX,
+        ),
+        (Bytes::from_static(&[0xff, 0xff, 0xff]), -1),
+        (Bytes::from_static(&[0xff, 0xff, 0x00]), -256),
+        (
+            Bytes::from_static(&[0x80, 0x00, 0x00]),
+            TRANSMISSION_OFFSET_MIN,
+        ),
+    ];
+
+    for (raw, offset) in tests {
+        let buf = &mut raw.clone();
+        let t1 = TransmissionOffsetExtension::unmarshal(buf)?;
+        let t2 = TransmissionOffsetExtension { offset };
+        assert_eq!(t1, t2);
+
+        let mut dst = BytesMut::with_capacity(t2.marshal_size());
+        dst.resize(t2.marshal_size(), 0);
+        t2.marshal_to(&mut dst)?;
+        assert_eq!(raw, dst.freeze());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_transmission_offset_extension_overflow() -> Result<()> {
+    for offset in [TRANSMISSION_OFFSET_MAX + 1, TRANSMISSION_OFFSET_MIN - 1] {
+        let t = TransmissionOffsetExtension { offset };
+
+        let mut dst = BytesMut::with_capacity(t.marshal_size());
+        dst.resize(t.marshal_size(), 0);
+        let result = t.marshal_to(&mut dst);
+        assert_eq!(Error::TransmissionOffsetOverflow, result.unwrap_err());
+    }
+
+    Ok(())
+}
@@ -8,6 +8,10 @@ use bytes::{Buf, BufMut};
 
 // transport-wide sequence
 pub const TRANSPORT_CC_EXTENSION_SIZE: usize = 2;
+// transport-wide sequence followed by a feedback request
+pub const TRANSPORT_CC_V2_EXTENSION_SIZE_WITH_FEEDBACK_REQUEST: usize = 4;
+// largest sequence count a feedback request can carry
+pub const TRANSPORT_CC_MAX_SEQUENCE_COUNT: u16 = 0x7FFF;
 
 /// TransportCCExtension is a extension payload format in
 /// https://tools.ietf.org/html/draft-holmer-rmcat-transport-wide-cc-extensions-01
@@ -58,3 +62,112 @@ impl Marshal for TransportCcExtension {
         Ok(TRANSPORT_CC_EXTENSION_SIZE)
     }
 }
+
+/// FeedbackRequest asks the receiver to send transport-wide feedback immediately
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct FeedbackRequest {
+    /// T: include timestamps in the feedback
+    pub include_timestamps: bool,
+    /// number of sequence numbers, ending with this packet's, to report on
+    pub sequence_count: u16,
+}
+
+/// TransportCcV2Extension is a extension payload format in
+/// http://www.webrtc.org/experiments/rtp-hdrext/transport-wide-cc-02
+/// The feedback request is optional, the extension is 2 bytes long without it.
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | L=3   |transport-wide sequence number |T|  seq count  |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |seq count cont.|
+/// +-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct TransportCcV2Extension {
+    pub transport_sequence: u16,
+    pub feedback_request: Option<FeedbackRequest>,
+}
+
+impl TransportCcV2Extension {
+    /// feedback_requested returns true if the sender asks for immediate feedback
+    pub fn feedback_requested(&self) -> bool {
+        self.feedback_request.is_some()
+    }
+}
+
+impl Unmarshal for TransportCcV2Extension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < TRANSPORT_CC_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        let transport_sequence = raw_packet.get_u16();
+
+        let feedback_request = match raw_packet.remaining() {
+            0 => None,
+            1 => return Err(Error::ErrBufferTooSmall.into()),
+            _ => {
+                let b = raw_packet.get_u16();
+                Some(FeedbackRequest {
+                    include_timestamps: (b & 0x8000) != 0,
+                    sequence_count: b & TRANSPORT_CC_MAX_SEQUENCE_COUNT,
+                })
+            }
+        };
+
+        Ok(TransportCcV2Extension {
+            transport_sequence,
+            feedback_request,
+        })
+    }
+}
+
+impl MarshalSize for TransportCcV2Extension {
+    /// MarshalSize returns the size of the TransportCcV2Extension once marshaled.
+    fn marshal_size(&self) -> usize {
+        if self.feedback_request.is_some() {
+            TRANSPORT_CC_V2_EXTENSION_SIZE_WITH_FEEDBACK_REQUEST
+        } else {
+            TRANSPORT_CC_EXTENSION_SIZE
+        }
+    }
+}
+
+impl Marshal for TransportCcV2Extension {
+    /// Marshal serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        if let Some(feedback_request) = &self.feedback_request {
+            if feedback_request.sequence_count > TRANSPORT_CC_MAX_SEQUENCE_COUNT {
+                return Err(Error::TransportCcSequenceCountOverflow.into());
+            }
+        }
+        let size = self.marshal_size();
+        if buf.remaining_mut() < size {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        buf.put_u16(self.transport_sequence);
+        if let Some(feedback_request) = &self.feedback_request {
+            let t = if feedback_request.include_timestamps {
+                0x8000u16
+            } else {
+                0u16
+            };
+            buf.put_u16(t | feedback_request.sequence_count);
+        }
+        Ok(size)
+    }
+}
+
+impl From<TransportCcExtension> for TransportCcV2Extension {
+    fn from(ext: TransportCcExtension) -> Self {
+        TransportCcV2Extension {
+            transport_sequence: ext.transport_sequence,
+            feedback_request: None,
+        }
+    }
+}
@@ -41,3 +41,69 @@ fn test_transport_cc_extension_extra_bytes() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_transport_cc_v2_extension() -> Result<()> {
+    let raw = Bytes::from_static(&[0x00, 0x02]);
+    let buf = &mut raw.clone();
+    let t1 = TransportCcV2Extension::unmarshal(buf)?;
+    let t2 = TransportCcV2Extension {
+        transport_sequence: 2,
+        feedback_request: None,
+    };
+    assert_eq!(t1, t2);
+    assert!(!t1.feedback_requested());
+
+    let mut dst = BytesMut::with_capacity(t2.marshal_size());
+    dst.resize(t2.marshal_size(), 0);
+    t2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_transport_cc_v2_extension_feedback_request() -> Result<()> {
+    let raw = Bytes::from_static(&[0x12, 0x34, 0x80, 0x0a]);
+    let buf = &mut raw.clone();
+    let t1 = TransportCcV2Extension::unmarshal(buf)?;
+    let t2 = TransportCcV2Extension {
+        transport_sequence: 0x1234,
+        feedback_request: Some(FeedbackRequest {
+            include_timestamps: true,
+            sequence_count: 10,
+        }),
+    };
+    assert_eq!(t1, t2);
+    assert!(t1.feedback_requested());
+
+    let mut dst = BytesMut::with_capacity(t2.marshal_size());
+    dst.resize(t2.marshal_size(), 0);
+    t2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_transport_cc_v2_extension_invalid() -> Result<()> {
+    let mut raw = Bytes::from_static(&[0x00, 0x02, 0x80]);
+    let result = TransportCcV2Extension::unmarshal(&mut raw);
+    assert!(result.is_err());
+
+    let t = TransportCcV2Extension {
+        transport_sequence: 2,
+        feedback_request: Some(FeedbackRequest {
+            include_timestamps: false,
+            sequence_count: 0x8000,
+        }),
+    };
+    let mut dst = BytesMut::with_capacity(t.marshal_size());
+    dst.resize(t.marshal_size(), 0);
+    let result = t.marshal_to(&mut dst);
+    assert_eq!(Error::TransportCcSequenceCountOverflow, result.unwrap_err());
+    // nothing is written on error
+    assert_eq!(&dst[..], &[0u8; 4][..]);
+
+    Ok(())
+}
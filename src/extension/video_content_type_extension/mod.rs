@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod video_content_type_extension_test;
+
+use crate::error::Error;
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use bytes::{Buf, BufMut};
+
+pub const VIDEO_CONTENT_TYPE_EXTENSION_SIZE: usize = 1;
+
+/// VideoContentType tells whether a video stream is camera or screen content
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VideoContentType {
+    #[default]
+    Unspecified = 0,
+    Screenshare = 1,
+}
+
+/// VideoContentTypeExtension is a extension payload format in
+/// http://www.webrtc.org/experiments/rtp-hdrext/video-content-type
+/// 0                   1
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=0 | Content type  |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct VideoContentTypeExtension {
+    pub content_type: VideoContentType,
+}
+
+impl Unmarshal for VideoContentTypeExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < VIDEO_CONTENT_TYPE_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let content_type = match raw_packet.get_u8() {
+            0 => VideoContentType::Unspecified,
+            1 => VideoContentType::Screenshare,
+            b => return Err(Error::VideoContentTypeInvalid(b).into()),
+        };
+
+        Ok(VideoContentTypeExtension { content_type })
+    }
+}
+
+impl MarshalSize for VideoContentTypeExtension {
+    /// MarshalSize returns the size of the VideoContentTypeExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        VIDEO_CONTENT_TYPE_EXTENSION_SIZE
+    }
+}
+
+impl Marshal for VideoContentTypeExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        if buf.remaining_mut() < VIDEO_CONTENT_TYPE_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        buf.put_u8(self.content_type as u8);
+
+        Ok(VIDEO_CONTENT_TYPE_EXTENSION_SIZE)
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+use crate::error::Result;
+use bytes::{Bytes, BytesMut};
+
+#[test]
+fn test_video_content_type_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 0][..];
+    let result = VideoContentTypeExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_video_content_type_extension() -> Result<()> {
+    let tests = vec![
+        (Bytes::from_static(&[0x00]), VideoContentType::Unspecified),
+        (Bytes::from_static(&[0x01]), VideoContentType::Screenshare),
+    ];
+
+    for (raw, content_type) in tests {
+        let buf = &mut raw.clone();
+        let v1 = VideoContentTypeExtension::unmarshal(buf)?;
+        let v2 = VideoContentTypeExtension { content_type };
+        assert_eq!(v1, v2);
+
+        let mut dst = BytesMut::with_capacity(v2.marshal_size());
+        dst.resize(v2.marshal_size(), 0);
+        v2.marshal_to(&mut dst)?;
+        assert_eq!(raw, dst.freeze());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_video_content_type_extension_invalid() -> Result<()> {
+    let mut raw = Bytes::from_static(&[0x02]);
+    let result = VideoContentTypeExtension::unmarshal(&mut raw);
+    assert_eq!(Error::VideoContentTypeInvalid(2), result.unwrap_err());
+
+    Ok(())
+}
@@ -0,0 +1,370 @@
+#[cfg(test)]
+mod video_layers_allocation_extension_test;
+
+use crate::error::Error;
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+pub const VIDEO_LAYERS_ALLOCATION_MAX_RTP_STREAMS: usize = 4;
+pub const VIDEO_LAYERS_ALLOCATION_MAX_SPATIAL_LAYERS: usize = 4;
+pub const VIDEO_LAYERS_ALLOCATION_MAX_TEMPORAL_LAYERS: usize = 4;
+// size of width, height and max framerate of a spatial layer
+const RESOLUTION_AND_FRAME_RATE_SIZE: usize = 5;
+
+/// SpatialLayer is an active spatial layer of one of the RTP streams
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct SpatialLayer {
+    pub rtp_stream_index: u8,
+    pub spatial_id: u8,
+    /// cumulative target bitrate of each temporal layer, in kbps
+    pub target_bitrate_kbps: Vec<u32>,
+    /// resolution and frame rate, only meaningful if
+    /// VideoLayersAllocationExtension::resolution_and_frame_rate_is_valid is set
+    pub width: u16,
+    pub height: u16,
+    pub frame_rate_fps: u8,
+}
+
+/// VideoLayersAllocationExtension is a extension payload format in
+/// http://www.webrtc.org/experiments/rtp-hdrext/video-layers-allocation00
+///
+/// An allocation without active spatial layers means the video is paused.
+///
+/// Format:
+///                           0 1 2 3 4 5 6 7
+///                          +-+-+-+-+-+-+-+-+
+///                          |RID| NS| sl_bm |
+///                          +-+-+-+-+-+-+-+-+
+/// Spatial layer bitmask     |sl0_bm |sl1_bm |
+///   up to 2 bytes           |---------------|
+///   when sl_bm == 0         |sl2_bm |sl3_bm |
+///                          +-+-+-+-+-+-+-+-+
+/// Number of temporal layers |#tl|#tl|#tl|#tl|
+/// per spatial layer         |   |   |   |   |
+///                          +-+-+-+-+-+-+-+-+
+///  Target bitrate in kpbs   |               |
+///   per temporal layer      :      ...      :
+///    leb128 encoded         |               |
+///                          +-+-+-+-+-+-+-+-+
+/// Resolution and framerate  |               |
+/// 5 bytes per spatial layer + width-1 for   +
+///      (optional)           | rid=0, sid=0  |
+///                          +---------------+
+///                          |               |
+///                          + height-1 for  +
+///                          | rid=0, sid=0  |
+///                          +---------------+
+///                          | max framerate |
+///                          +-+-+-+-+-+-+-+-+
+///                          :      ...      :
+///                          +-+-+-+-+-+-+-+-+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct VideoLayersAllocationExtension {
+    /// index of the RTP stream the extension is sent on
+    pub rtp_stream_index: u8,
+    pub resolution_and_frame_rate_is_valid: bool,
+    /// active spatial layers, ordered by RTP stream index and spatial id
+    pub active_spatial_layers: Vec<SpatialLayer>,
+}
+
+impl VideoLayersAllocationExtension {
+    /// num_rtp_streams returns the number of RTP streams with active layers,
+    /// clamped to the maximum so that sizing an invalid allocation is safe
+    fn num_rtp_streams(&self) -> usize {
+        self.active_spatial_layers
+            .iter()
+            .map(|layer| layer.rtp_stream_index as usize + 1)
+            .max()
+            .unwrap_or_default()
+            .min(VIDEO_LAYERS_ALLOCATION_MAX_RTP_STREAMS)
+    }
+
+    /// spatial_layer_bitmasks returns the bitmask of active spatial layers of each
+    /// RTP stream, skipping out of range layers which fail validation
+    fn spatial_layer_bitmasks(&self) -> [u8; VIDEO_LAYERS_ALLOCATION_MAX_RTP_STREAMS] {
+        let mut bitmasks = [0u8; VIDEO_LAYERS_ALLOCATION_MAX_RTP_STREAMS];
+        for layer in &self.active_spatial_layers {
+            if (layer.rtp_stream_index as usize) < VIDEO_LAYERS_ALLOCATION_MAX_RTP_STREAMS
+                && (layer.spatial_id as usize) < VIDEO_LAYERS_ALLOCATION_MAX_SPATIAL_LAYERS
+            {
+                bitmasks[layer.rtp_stream_index as usize] |= 1 << layer.spatial_id;
+            }
+        }
+        bitmasks
+    }
+
+    /// shared_spatial_layer_bitmask returns the bitmask used by all RTP streams, or 0
+    /// if the streams use different bitmasks
+    fn shared_spatial_layer_bitmask(&self) -> u8 {
+        let bitmasks = self.spatial_layer_bitmasks();
+        let num_rtp_streams = self.num_rtp_streams();
+        if bitmasks[..num_rtp_streams]
+            .iter()
+            .all(|bitmask| *bitmask == bitmasks[0])
+        {
+            bitmasks[0]
+        } else {
+            0
+        }
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.rtp_stream_index as usize >= VIDEO_LAYERS_ALLOCATION_MAX_RTP_STREAMS {
+            return Err(Error::VideoLayersAllocationInvalid);
+        }
+        for layer in &self.active_spatial_layers {
+            if layer.rtp_stream_index as usize >= VIDEO_LAYERS_ALLOCATION_MAX_RTP_STREAMS
+                || layer.spatial_id as usize >= VIDEO_LAYERS_ALLOCATION_MAX_SPATIAL_LAYERS
+                || layer.target_bitrate_kbps.is_empty()
+                || layer.target_bitrate_kbps.len() > VIDEO_LAYERS_ALLOCATION_MAX_TEMPORAL_LAYERS
+                || (self.resolution_and_frame_rate_is_valid
+                    && (layer.width == 0 || layer.height == 0))
+            {
+                return Err(Error::VideoLayersAllocationInvalid);
+            }
+        }
+        for pair in self.active_spatial_layers.windows(2) {
+            // layers are sent in the order of the bitmasks
+            if (pair[0].rtp_stream_index, pair[0].spatial_id)
+                >= (pair[1].rtp_stream_index, pair[1].spatial_id)
+            {
+                return Err(Error::VideoLayersAllocationInvalid);
+            }
+        }
+        if !self.active_spatial_layers.is_empty()
+            && self.rtp_stream_index as usize >= self.num_rtp_streams()
+        {
+            return Err(Error::VideoLayersAllocationInvalid);
+        }
+        Ok(())
+    }
+}
+
+impl Unmarshal for VideoLayersAllocationExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() == 0 {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let b0 = raw_packet.get_u8();
+        let rtp_stream_index = b0 >> 6;
+        let num_rtp_streams = ((b0 >> 4) & 0x3) as usize + 1;
+        let shared_bitmask = b0 & 0xF;
+
+        if b0 == 0 && !raw_packet.has_remaining() {
+            // no active layers, the video is paused
+            return Ok(VideoLayersAllocationExtension::default());
+        }
+        if rtp_stream_index as usize >= num_rtp_streams {
+            return Err(Error::VideoLayersAllocationInvalid.into());
+        }
+
+        let mut bitmasks = [shared_bitmask; VIDEO_LAYERS_ALLOCATION_MAX_RTP_STREAMS];
+        if shared_bitmask == 0 {
+            let size = num_rtp_streams.div_ceil(2);
+            if raw_packet.remaining() < size {
+                return Err(Error::ErrBufferTooSmall.into());
+            }
+            for i in 0..size {
+                let b = raw_packet.get_u8();
+                bitmasks[2 * i] = b >> 4;
+                bitmasks[2 * i + 1] = b & 0xF;
+            }
+        }
+
+        let mut active_spatial_layers = vec![];
+        for (rtp_stream_index, bitmask) in bitmasks[..num_rtp_streams].iter().enumerate() {
+            for spatial_id in 0..VIDEO_LAYERS_ALLOCATION_MAX_SPATIAL_LAYERS {
+                if bitmask & (1 << spatial_id) != 0 {
+                    active_spatial_layers.push(SpatialLayer {
+                        rtp_stream_index: rtp_stream_index as u8,
+                        spatial_id: spatial_id as u8,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+        if active_spatial_layers.is_empty() {
+            return Err(Error::VideoLayersAllocationInvalid.into());
+        }
+
+        // 2 bits for the number of temporal layers of each spatial layer
+        let mut num_temporal_layers = vec![];
+        let size = active_spatial_layers.len().div_ceil(4);
+        if raw_packet.remaining() < size {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        for _ in 0..size {
+            let b = raw_packet.get_u8();
+            for shift in [6, 4, 2, 0] {
+                num_temporal_layers.push(((b >> shift) & 0x3) as usize + 1);
+            }
+        }
+
+        for (layer, num_temporal_layers) in
+            active_spatial_layers.iter_mut().zip(num_temporal_layers)
+        {
+            for _ in 0..num_temporal_layers {
+                let bitrate = read_leb128(raw_packet)?;
+                layer.target_bitrate_kbps.push(bitrate);
+            }
+        }
+
+        let resolution_and_frame_rate_is_valid = raw_packet.has_remaining();
+        if resolution_and_frame_rate_is_valid {
+            if raw_packet.remaining()
+                != active_spatial_layers.len() * RESOLUTION_AND_FRAME_RATE_SIZE
+            {
+                return Err(Error::VideoLayersAllocationInvalid.into());
+            }
+            for layer in active_spatial_layers.iter_mut() {
+                layer.width = raw_packet.get_u16() + 1;
+                layer.height = raw_packet.get_u16() + 1;
+                layer.frame_rate_fps = raw_packet.get_u8();
+            }
+        }
+
+        Ok(VideoLayersAllocationExtension {
+            rtp_stream_index,
+            resolution_and_frame_rate_is_valid,
+            active_spatial_layers,
+        })
+    }
+}
+
+impl MarshalSize for VideoLayersAllocationExtension {
+    /// MarshalSize returns the size of the VideoLayersAllocationExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        if self.active_spatial_layers.is_empty() {
+            return 1;
+        }
+
+        let mut size = 1;
+        if self.shared_spatial_layer_bitmask() == 0 {
+            size += self.num_rtp_streams().div_ceil(2);
+        }
+        size += self.active_spatial_layers.len().div_ceil(4);
+        for layer in &self.active_spatial_layers {
+            size += layer
+                .target_bitrate_kbps
+                .iter()
+                .map(|bitrate| leb128_size(*bitrate))
+                .sum::<usize>();
+        }
+        if self.resolution_and_frame_rate_is_valid {
+            size += self.active_spatial_layers.len() * RESOLUTION_AND_FRAME_RATE_SIZE;
+        }
+        size
+    }
+}
+
+impl Marshal for VideoLayersAllocationExtension {
+    /// Marshal validates the allocation before sizing the buffer
+    fn marshal(&self) -> Result<Bytes, util::Error> {
+        self.validate()?;
+        let size = self.marshal_size();
+        let mut buf = BytesMut::with_capacity(size);
+        buf.resize(size, 0);
+        let n = self.marshal_to(&mut buf)?;
+        buf.truncate(n);
+        Ok(buf.freeze())
+    }
+
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        self.validate()?;
+        let size = self.marshal_size();
+        if buf.remaining_mut() < size {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        if self.active_spatial_layers.is_empty() {
+            buf.put_u8(0);
+            return Ok(size);
+        }
+
+        let num_rtp_streams = self.num_rtp_streams();
+        let shared_bitmask = self.shared_spatial_layer_bitmask();
+        buf.put_u8(
+            self.rtp_stream_index << 6 | ((num_rtp_streams - 1) as u8) << 4 | shared_bitmask,
+        );
+
+        if shared_bitmask == 0 {
+            let bitmasks = self.spatial_layer_bitmasks();
+            for pair in bitmasks[..num_rtp_streams].chunks(2) {
+                let low = pair.get(1).copied().unwrap_or_default();
+                buf.put_u8(pair[0] << 4 | low);
+            }
+        }
+
+        for layers in self.active_spatial_layers.chunks(4) {
+            let mut b = 0u8;
+            for (i, layer) in layers.iter().enumerate() {
+                b |= ((layer.target_bitrate_kbps.len() - 1) as u8) << (6 - 2 * i);
+            }
+            buf.put_u8(b);
+        }
+
+        for layer in &self.active_spatial_layers {
+            for bitrate in &layer.target_bitrate_kbps {
+                write_leb128(&mut buf, *bitrate);
+            }
+        }
+
+        if self.resolution_and_frame_rate_is_valid {
+            for layer in &self.active_spatial_layers {
+                buf.put_u16(layer.width - 1);
+                buf.put_u16(layer.height - 1);
+                buf.put_u8(layer.frame_rate_fps);
+            }
+        }
+
+        Ok(size)
+    }
+}
+
+/// leb128_size returns the number of bytes needed to LEB128 encode v
+fn leb128_size(mut v: u32) -> usize {
+    let mut size = 1;
+    while v >= 0x80 {
+        v >>= 7;
+        size += 1;
+    }
+    size
+}
+
+fn read_leb128<B>(raw_packet: &mut B) -> Result<u32, Error>
+where
+    B: Buf,
+{
+    let mut v = 0u64;
+    for i in 0..leb128_size(u32::MAX) {
+        if !raw_packet.has_remaining() {
+            return Err(Error::ErrBufferTooSmall);
+        }
+        let b = raw_packet.get_u8();
+        v |= ((b & 0x7F) as u64) << (7 * i);
+        if b & 0x80 == 0 {
+            return if v > u32::MAX as u64 {
+                Err(Error::VideoLayersAllocationInvalid)
+            } else {
+                Ok(v as u32)
+            };
+        }
+    }
+    Err(Error::VideoLayersAllocationInvalid)
+}
+
+fn write_leb128(buf: &mut &mut [u8], mut v: u32) {
+    while v >= 0x80 {
+        buf.put_u8(0x80 | (v & 0x7F) as u8);
+        v >>= 7;
+    }
+    buf.put_u8(v as u8);
+}
@@ -0,0 +1,190 @@
+use super::*;
+use crate::error::Result;
+use bytes::{Bytes, BytesMut};
+
+fn roundtrip(allocation: &VideoLayersAllocationExtension) -> Result<Bytes> {
+    let mut raw = BytesMut::with_capacity(allocation.marshal_size());
+    raw.resize(allocation.marshal_size(), 0);
+    allocation.marshal_to(&mut raw)?;
+    let raw = raw.freeze();
+
+    let buf = &mut raw.clone();
+    let parsed = VideoLayersAllocationExtension::unmarshal(buf)?;
+    assert_eq!(allocation, &parsed);
+
+    Ok(raw)
+}
+
+#[test]
+fn test_video_layers_allocation_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 0][..];
+    let result = VideoLayersAllocationExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    // one spatial layer with one temporal layer, missing its bitrate
+    let mut buf = Bytes::from_static(&[0x01, 0x00]);
+    let result = VideoLayersAllocationExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_video_layers_allocation_extension_paused() -> Result<()> {
+    let allocation = VideoLayersAllocationExtension::default();
+    let raw = roundtrip(&allocation)?;
+    assert_eq!(raw, Bytes::from_static(&[0x00]));
+
+    Ok(())
+}
+
+#[test]
+fn test_video_layers_allocation_extension_simulcast() -> Result<()> {
+    // three simulcast streams with a single spatial layer and two temporal layers each
+    let allocation = VideoLayersAllocationExtension {
+        rtp_stream_index: 1,
+        resolution_and_frame_rate_is_valid: false,
+        active_spatial_layers: (0..3)
+            .map(|rtp_stream_index| SpatialLayer {
+                rtp_stream_index,
+                spatial_id: 0,
+                target_bitrate_kbps: vec![100 << rtp_stream_index, 150 << rtp_stream_index],
+                ..Default::default()
+            })
+            .collect(),
+    };
+
+    let raw = roundtrip(&allocation)?;
+    assert_eq!(
+        raw,
+        Bytes::from_static(&[
+            0x61, // RID=1, NS=2, sl_bm=0b0001
+            0x54, // two temporal layers for each of the three spatial layers
+            0x64, 0x96, 0x01, // 100, 150
+            0xc8, 0x01, 0xac, 0x02, // 200, 300
+            0x90, 0x03, 0xd8, 0x04, // 400, 600
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_video_layers_allocation_extension_resolution() -> Result<()> {
+    // stream 0 sends spatial layers 0 and 1, stream 1 only spatial layer 0
+    let allocation = VideoLayersAllocationExtension {
+        rtp_stream_index: 0,
+        resolution_and_frame_rate_is_valid: true,
+        active_spatial_layers: vec![
+            SpatialLayer {
+                rtp_stream_index: 0,
+                spatial_id: 0,
+                target_bitrate_kbps: vec![50],
+                width: 320,
+                height: 180,
+                frame_rate_fps: 15,
+            },
+            SpatialLayer {
+                rtp_stream_index: 0,
+                spatial_id: 1,
+                target_bitrate_kbps: vec![100, 200, 300],
+                width: 640,
+                height: 360,
+                frame_rate_fps: 30,
+            },
+            SpatialLayer {
+                rtp_stream_index: 1,
+                spatial_id: 0,
+                target_bitrate_kbps: vec![1000],
+                width: 1280,
+                height: 720,
+                frame_rate_fps: 30,
+            },
+        ],
+    };
+
+    let raw = roundtrip(&allocation)?;
+    // individual bitmasks since the streams differ
+    assert_eq!(raw[0], 0x10);
+    assert_eq!(raw[1], 0x31);
+
+    Ok(())
+}
+
+#[test]
+fn test_video_layers_allocation_extension_invalid() -> Result<()> {
+    // RID=1 with a single RTP stream
+    let mut buf = Bytes::from_static(&[0x41, 0x00, 0x64]);
+    let result = VideoLayersAllocationExtension::unmarshal(&mut buf);
+    assert_eq!(Error::VideoLayersAllocationInvalid, result.unwrap_err());
+
+    // truncated resolution block
+    let mut buf = Bytes::from_static(&[0x01, 0x00, 0x64, 0x01, 0x3f]);
+    let result = VideoLayersAllocationExtension::unmarshal(&mut buf);
+    assert_eq!(Error::VideoLayersAllocationInvalid, result.unwrap_err());
+
+    let allocation = VideoLayersAllocationExtension {
+        rtp_stream_index: 0,
+        resolution_and_frame_rate_is_valid: false,
+        active_spatial_layers: vec![
+            SpatialLayer {
+                spatial_id: 1,
+                target_bitrate_kbps: vec![100],
+                ..Default::default()
+            },
+            SpatialLayer {
+                spatial_id: 0,
+                target_bitrate_kbps: vec![100],
+                ..Default::default()
+            },
+        ],
+    };
+    let mut raw = BytesMut::with_capacity(allocation.marshal_size());
+    raw.resize(allocation.marshal_size(), 0);
+    let result = allocation.marshal_to(&mut raw);
+    assert_eq!(Error::VideoLayersAllocationInvalid, result.unwrap_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_video_layers_allocation_extension_out_of_range() -> Result<()> {
+    for (rtp_stream_index, spatial_id) in [(5, 0), (0, 9), (255, 255)] {
+        let allocation = VideoLayersAllocationExtension {
+            rtp_stream_index: 0,
+            resolution_and_frame_rate_is_valid: false,
+            active_spatial_layers: vec![SpatialLayer {
+                rtp_stream_index,
+                spatial_id,
+                target_bitrate_kbps: vec![100],
+                ..Default::default()
+            }],
+        };
+        let result = allocation.marshal();
+        match result {
+            Err(err) => assert_eq!(Error::VideoLayersAllocationInvalid, err),
+            Ok(_) => panic!("expected an invalid allocation"),
+        }
+
+        let mut raw = vec![0u8; allocation.marshal_size()];
+        let result = allocation.marshal_to(&mut raw);
+        assert_eq!(Error::VideoLayersAllocationInvalid, result.unwrap_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_leb128() -> Result<()> {
+    for v in [0, 1, 127, 128, 300, 16383, 16384, u32::MAX] {
+        let mut raw = vec![0u8; leb128_size(v)];
+        let mut buf = &mut raw[..];
+        write_leb128(&mut buf, v);
+        assert!(buf.is_empty());
+
+        let mut reader = &raw[..];
+        assert_eq!(v, read_leb128(&mut reader)?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod video_timing_extension_test;
+
+use crate::error::Error;
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use bytes::{Buf, BufMut};
+
+pub const VIDEO_TIMING_EXTENSION_SIZE: usize = 13;
+// size of the old wire format without the flags field
+pub const VIDEO_TIMING_EXTENSION_SIZE_WITHOUT_FLAGS: usize = 12;
+
+// the frame was sent because a timer fired
+pub const VIDEO_TIMING_FLAG_TRIGGERED_BY_TIMER: u8 = 0x01;
+// the frame was sent because it was larger than the outlier threshold
+pub const VIDEO_TIMING_FLAG_TRIGGERED_BY_SIZE: u8 = 0x02;
+// timing information is not valid
+pub const VIDEO_TIMING_FLAG_INVALID: u8 = 0xFF;
+
+/// VideoTimingExtension is a extension payload format in
+/// http://www.webrtc.org/experiments/rtp-hdrext/video-timing
+///
+/// All deltas are in milliseconds relative to the capture time of the frame.
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=12|     flags     |     encode start ms delta     |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |    encode finish ms delta     |  packetizer finish ms delta   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |     pacer exit ms delta       |  network timestamp ms delta   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  network2 timestamp ms delta  |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct VideoTimingExtension {
+    pub flags: u8,
+    pub encode_start_delta_ms: u16,
+    pub encode_finish_delta_ms: u16,
+    pub packetization_finish_delta_ms: u16,
+    pub pacer_exit_delta_ms: u16,
+    /// set by the first network element that forwards the packet, e.g. an SFU
+    pub network_timestamp_delta_ms: u16,
+    /// set by the second network element that forwards the packet
+    pub network2_timestamp_delta_ms: u16,
+}
+
+impl Unmarshal for VideoTimingExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let flags = if raw_packet.remaining() >= VIDEO_TIMING_EXTENSION_SIZE {
+            raw_packet.get_u8()
+        } else if raw_packet.remaining() == VIDEO_TIMING_EXTENSION_SIZE_WITHOUT_FLAGS {
+            0
+        } else {
+            return Err(Error::ErrBufferTooSmall.into());
+        };
+
+        Ok(VideoTimingExtension {
+            flags,
+            encode_start_delta_ms: raw_packet.get_u16(),
+            encode_finish_delta_ms: raw_packet.get_u16(),
+            packetization_finish_delta_ms: raw_packet.get_u16(),
+            pacer_exit_delta_ms: raw_packet.get_u16(),
+            network_timestamp_delta_ms: raw_packet.get_u16(),
+            network2_timestamp_delta_ms: raw_packet.get_u16(),
+        })
+    }
+}
+
+impl MarshalSize for VideoTimingExtension {
+    /// MarshalSize returns the size of the VideoTimingExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        VIDEO_TIMING_EXTENSION_SIZE
+    }
+}
+
+impl Marshal for VideoTimingExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        if buf.remaining_mut() < VIDEO_TIMING_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        buf.put_u8(self.flags);
+        buf.put_u16(self.encode_start_delta_ms);
+        buf.put_u16(self.encode_finish_delta_ms);
+        buf.put_u16(self.packetization_finish_delta_ms);
+        buf.put_u16(self.pacer_exit_delta_ms);
+        buf.put_u16(self.network_timestamp_delta_ms);
+        buf.put_u16(self.network2_timestamp_delta_ms);
+
+        Ok(VIDEO_TIMING_EXTENSION_SIZE)
+    }
+}
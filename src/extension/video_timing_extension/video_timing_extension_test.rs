@@ -0,0 +1,58 @@
+use super::*;
+use crate::error::Result;
+use bytes::{Bytes, BytesMut};
+
+#[test]
+fn test_video_timing_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; VIDEO_TIMING_EXTENSION_SIZE_WITHOUT_FLAGS - 1][..];
+    let result = VideoTimingExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_video_timing_extension() -> Result<()> {
+    let raw = Bytes::from_static(&[
+        0x01, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04, 0x01, 0x00, 0xff, 0xff,
+    ]);
+    let buf = &mut raw.clone();
+    let v1 = VideoTimingExtension::unmarshal(buf)?;
+    let v2 = VideoTimingExtension {
+        flags: VIDEO_TIMING_FLAG_TRIGGERED_BY_TIMER,
+        encode_start_delta_ms: 1,
+        encode_finish_delta_ms: 2,
+        packetization_finish_delta_ms: 3,
+        pacer_exit_delta_ms: 4,
+        network_timestamp_delta_ms: 256,
+        network2_timestamp_delta_ms: 0xffff,
+    };
+    assert_eq!(v1, v2);
+
+    let mut dst = BytesMut::with_capacity(v2.marshal_size());
+    dst.resize(v2.marshal_size(), 0);
+    v2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_video_timing_extension_without_flags() -> Result<()> {
+    let mut raw = Bytes::from_static(&[
+        0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00,
+    ]);
+    let v1 = VideoTimingExtension::unmarshal(&mut raw)?;
+    let v2 = VideoTimingExtension {
+        flags: 0,
+        encode_start_delta_ms: 1,
+        encode_finish_delta_ms: 2,
+        packetization_finish_delta_ms: 3,
+        pacer_exit_delta_ms: 4,
+        network_timestamp_delta_ms: 0,
+        network2_timestamp_delta_ms: 0,
+    };
+    assert_eq!(v1, v2);
+
+    Ok(())
+}
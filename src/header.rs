@@ -1,4 +1,5 @@
 use crate::error::Error;
+use crate::extension::sdes_extension::*;
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
 
 use bytes::{Buf, BufMut, Bytes};
@@ -49,6 +50,19 @@ pub struct Header {
     pub extensions: Vec<Extension>,
 }
 
+/// ParseMode selects how strictly packets are validated while unmarshaling
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Lenient accepts any version, reserved extension ids and extension
+    /// elements overrunning the extension block, as Unmarshal does
+    #[default]
+    Lenient,
+    /// Strict rejects packets whose version is not 2, reserved extension ids,
+    /// extension elements overrunning the extension block and invalid padding,
+    /// with errors reporting the byte offset and the field that failed
+    Strict,
+}
+
 impl Unmarshal for Header {
     /// Unmarshal parses the passed byte slice and stores the result in the Header this method is called upon
     fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
@@ -56,9 +70,31 @@ impl Unmarshal for Header {
         Self: Sized,
         B: Buf,
     {
+        Header::unmarshal_with_mode(raw_packet, ParseMode::Lenient)
+    }
+}
+
+impl Header {
+    /// unmarshal_with_mode parses the passed byte slice with the given ParseMode
+    pub fn unmarshal_with_mode<B>(raw_packet: &mut B, mode: ParseMode) -> Result<Self, util::Error>
+    where
+        B: Buf,
+    {
+        let strict = mode == ParseMode::Strict;
+        let fail = |lenient: Error, strict_err: Error| -> util::Error {
+            if strict {
+                strict_err.into()
+            } else {
+                lenient.into()
+            }
+        };
+
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < HEADER_LENGTH {
-            return Err(Error::ErrHeaderSizeInsufficient.into());
+            return Err(fail(
+                Error::ErrHeaderSizeInsufficient,
+                Error::ErrFieldTruncated(0, "fixed header"),
+            ));
         }
         /*
          *  0                   1                   2                   3
@@ -80,9 +116,20 @@ impl Unmarshal for Header {
         let extension = (b0 >> EXTENSION_SHIFT & EXTENSION_MASK) > 0;
         let cc = (b0 & CC_MASK) as usize;
 
+        if strict && version != 2 {
+            return Err(Error::ErrFieldInvalid(0, "version").into());
+        }
+
         let mut curr_offset = CSRC_OFFSET + (cc * CSRC_LENGTH);
         if raw_packet_len < curr_offset {
-            return Err(Error::ErrHeaderSizeInsufficient.into());
+            return Err(fail(
+                Error::ErrHeaderSizeInsufficient,
+                if raw_packet_len < CSRC_OFFSET {
+                    Error::ErrFieldTruncated(0, "fixed header")
+                } else {
+                    Error::ErrFieldTruncated(CSRC_OFFSET, "csrc")
+                },
+            ));
         }
 
         let b1 = raw_packet.get_u8();
@@ -101,7 +148,10 @@ impl Unmarshal for Header {
         let (extension_profile, extensions) = if extension {
             let expected = curr_offset + 4;
             if raw_packet_len < expected {
-                return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                return Err(fail(
+                    Error::ErrHeaderSizeInsufficientForExtension,
+                    Error::ErrFieldTruncated(curr_offset, "extension header"),
+                ));
             }
             let extension_profile = raw_packet.get_u16();
             curr_offset += 2;
@@ -110,7 +160,10 @@ impl Unmarshal for Header {
 
             let expected = curr_offset + extension_length;
             if raw_packet_len < expected {
-                return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                return Err(fail(
+                    Error::ErrHeaderSizeInsufficientForExtension,
+                    Error::ErrFieldTruncated(curr_offset - 2, "extension length"),
+                ));
             }
 
             let mut extensions = vec![];
@@ -128,11 +181,24 @@ impl Unmarshal for Header {
 
                         let extid = b >> 4;
                         let len = ((b & (0xFF ^ 0xF0)) + 1) as usize;
-                        curr_offset += 1;
 
                         if extid == EXTENSION_ID_RESERVED {
+                            if strict {
+                                return Err(
+                                    Error::ErrFieldInvalid(curr_offset, "extension id").into()
+                                );
+                            }
                             break;
                         }
+                        if strict && curr_offset + 1 + len > end {
+                            return Err(
+                                Error::ErrFieldTruncated(curr_offset, "extension element").into()
+                            );
+                        }
+                        if raw_packet.remaining() < len {
+                            return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                        }
+                        curr_offset += 1;
 
                         extensions.push(Extension {
                             id: extid,
@@ -153,10 +219,23 @@ impl Unmarshal for Header {
                         }
 
                         let extid = b;
-                        curr_offset += 1;
-
+                        if (strict && curr_offset + 2 > end) || raw_packet.remaining() < 1 {
+                            return Err(fail(
+                                Error::ErrHeaderSizeInsufficientForExtension,
+                                Error::ErrFieldTruncated(curr_offset, "extension element"),
+                            ));
+                        }
                         let len = raw_packet.get_u8() as usize;
-                        curr_offset += 1;
+
+                        if strict && curr_offset + 2 + len > end {
+                            return Err(
+                                Error::ErrFieldTruncated(curr_offset, "extension element").into()
+                            );
+                        }
+                        if raw_packet.remaining() < len {
+                            return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                        }
+                        curr_offset += 2;
 
                         extensions.push(Extension {
                             id: extid,
@@ -167,9 +246,6 @@ impl Unmarshal for Header {
                 }
                 // RFC3550 Extension
                 _ => {
-                    if raw_packet_len < curr_offset + extension_length {
-                        return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
-                    }
                     extensions.push(Extension {
                         id: 0,
                         payload: raw_packet.copy_to_bytes(extension_length),
@@ -408,6 +484,42 @@ impl Header {
         }
     }
 
+    /// returns the payload of an SDES item extension with the given id, checking
+    /// its size against the extension profile of the header
+    fn get_sdes_item(&self, id: u8) -> Result<Option<Bytes>, util::Error> {
+        match self.get_extension(id) {
+            Some(payload) => {
+                validate_sdes_item_size(payload.len(), self.extension_profile)?;
+                Ok(Some(payload))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// returns the urn:ietf:params:rtp-hdrext:sdes:mid extension with the given id
+    pub fn get_mid(&self, id: u8) -> Result<Option<MidExtension>, util::Error> {
+        self.get_sdes_item(id)?
+            .map(|mut payload| MidExtension::unmarshal(&mut payload))
+            .transpose()
+    }
+
+    /// returns the urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id extension with the given id
+    pub fn get_rid(&self, id: u8) -> Result<Option<RtpStreamIdExtension>, util::Error> {
+        self.get_sdes_item(id)?
+            .map(|mut payload| RtpStreamIdExtension::unmarshal(&mut payload))
+            .transpose()
+    }
+
+    /// returns the urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id extension with the given id
+    pub fn get_repaired_rid(
+        &self,
+        id: u8,
+    ) -> Result<Option<RepairedRtpStreamIdExtension>, util::Error> {
+        self.get_sdes_item(id)?
+            .map(|mut payload| RepairedRtpStreamIdExtension::unmarshal(&mut payload))
+            .transpose()
+    }
+
     /// Removes an RTP Header extension
     pub fn del_extension(&mut self, id: u8) -> Result<(), Error> {
         if self.extension {
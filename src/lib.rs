@@ -2,11 +2,17 @@
 #![allow(dead_code)]
 
 pub mod codecs;
+pub mod demux;
 mod error;
 pub mod extension;
 pub mod header;
 pub mod packet;
+pub mod packet_history;
 pub mod packetizer;
 pub mod sequence;
+pub mod sfu;
+pub mod stats;
+#[cfg(test)]
+mod test_util;
 
 pub use error::Error;
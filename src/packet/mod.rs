@@ -13,6 +13,10 @@ use std::fmt;
 pub struct Packet {
     pub header: Header,
     pub payload: Bytes,
+    /// number of padding bytes appended when header.padding is set, 0 pads the
+    /// payload to a multiple of 4 bytes. Unmarshal keeps the received padding
+    /// size so the packet is marshaled back to the same length.
+    pub padding_size: u8,
 }
 
 impl fmt::Display for Packet {
@@ -38,25 +42,51 @@ impl Unmarshal for Packet {
         Self: Sized,
         B: Buf,
     {
-        let header = Header::unmarshal(raw_packet)?;
+        Packet::unmarshal_with_mode(raw_packet, ParseMode::Lenient)
+    }
+}
+
+impl Packet {
+    /// unmarshal_with_mode parses the passed byte slice with the given ParseMode
+    pub fn unmarshal_with_mode<B>(raw_packet: &mut B, mode: ParseMode) -> Result<Self, util::Error>
+    where
+        B: Buf,
+    {
+        let raw_packet_len = raw_packet.remaining();
+        let header = Header::unmarshal_with_mode(raw_packet, mode)?;
         let payload_len = raw_packet.remaining();
         let payload = raw_packet.copy_to_bytes(payload_len);
         if header.padding {
+            let padding_offset = raw_packet_len - 1;
             if payload_len > 0 {
                 let padding_len = payload[payload_len - 1] as usize;
-                if padding_len <= payload_len {
+                // a padding length of 0 cannot be marshaled back, padding_size 0
+                // pads to a multiple of 4 bytes. Lenient mode strips nothing, as
+                // Unmarshal always did.
+                if padding_len == 0 && mode == ParseMode::Strict {
+                    Err(Error::ErrFieldInvalid(padding_offset, "padding length").into())
+                } else if padding_len <= payload_len {
                     Ok(Packet {
                         header,
                         payload: payload.slice(..payload_len - padding_len),
+                        padding_size: padding_len as u8,
                     })
+                } else if mode == ParseMode::Strict {
+                    Err(Error::ErrFieldInvalid(padding_offset, "padding length").into())
                 } else {
                     Err(Error::ErrShortPacket.into())
                 }
+            } else if mode == ParseMode::Strict {
+                Err(Error::ErrFieldTruncated(raw_packet_len, "padding length").into())
             } else {
                 Err(Error::ErrShortPacket.into())
             }
         } else {
-            Ok(Packet { header, payload })
+            Ok(Packet {
+                header,
+                payload,
+                ..Default::default()
+            })
         }
     }
 }
@@ -64,18 +94,7 @@ impl Unmarshal for Packet {
 impl MarshalSize for Packet {
     /// MarshalSize returns the size of the packet once marshaled.
     fn marshal_size(&self) -> usize {
-        let payload_len = self.payload.len();
-        let padding_len = if self.header.padding {
-            let padding_len = get_padding(payload_len);
-            if padding_len == 0 {
-                4
-            } else {
-                padding_len
-            }
-        } else {
-            0
-        };
-        self.header.marshal_size() + payload_len + padding_len
+        self.header.marshal_size() + self.payload.len() + self.padding_len()
     }
 }
 
@@ -89,27 +108,46 @@ impl Marshal for Packet {
         let n = self.header.marshal_to(buf)?;
         buf = &mut buf[n..];
         buf.put(&*self.payload);
-        let padding_len = if self.header.padding {
-            let mut padding_len = get_padding(self.payload.len());
-            if padding_len == 0 {
-                padding_len = 4;
-            }
-            for i in 0..padding_len {
-                if i != padding_len - 1 {
-                    buf.put_u8(0);
-                } else {
-                    buf.put_u8(padding_len as u8);
-                }
+        let padding_len = self.padding_len();
+        for i in 0..padding_len {
+            if i != padding_len - 1 {
+                buf.put_u8(0);
+            } else {
+                buf.put_u8(padding_len as u8);
             }
-            padding_len
-        } else {
-            0
-        };
+        }
 
         Ok(n + self.payload.len() + padding_len)
     }
 }
 
+impl Packet {
+    /// set_padding_size sets the header padding bit and the exact number of
+    /// padding bytes appended to the payload, between 1 and 255
+    pub fn set_padding_size(&mut self, padding_size: usize) -> Result<(), Error> {
+        if padding_size == 0 || padding_size > u8::MAX as usize {
+            return Err(Error::ErrPaddingSizeInvalid(padding_size));
+        }
+        self.header.padding = true;
+        self.padding_size = padding_size as u8;
+        Ok(())
+    }
+
+    /// padding_len returns the number of padding bytes appended to the payload
+    fn padding_len(&self) -> usize {
+        if !self.header.padding {
+            0
+        } else if self.padding_size != 0 {
+            self.padding_size as usize
+        } else {
+            match get_padding(self.payload.len()) {
+                0 => 4,
+                padding_len => padding_len,
+            }
+        }
+    }
+}
+
 /// getPadding Returns the padding required to make the length a multiple of 4
 fn get_padding(len: usize) -> usize {
     if len % 4 == 0 {
@@ -34,6 +34,7 @@ fn test_basic() -> Result<()> {
             ..Default::default()
         },
         payload: Bytes::from_static(&[0x98, 0x36, 0xbe, 0x88, 0x9e]),
+        ..Default::default()
     };
     let buf = &mut raw_pkt.clone();
     let packet = Packet::unmarshal(buf)?;
@@ -107,6 +108,7 @@ fn test_extension() -> Result<()> {
             ..Default::default()
         },
         payload: Bytes::from_static(&[]),
+        ..Default::default()
     };
 
     let mut raw = BytesMut::new();
@@ -196,6 +198,7 @@ fn test_rfc_8285_one_byte_extension() -> Result<()> {
             ..Default::default()
         },
         payload: raw_pkt.slice(20..),
+        ..Default::default()
     };
 
     let dst = p.marshal()?;
@@ -262,6 +265,7 @@ fn test_rfc_8285_one_byte_two_extension_of_two_bytes() -> Result<()> {
             ..Default::default()
         },
         payload: raw_pkt.slice(20..),
+        ..Default::default()
     };
 
     let dst = p.marshal()?;
@@ -397,6 +401,7 @@ fn test_rfc_8285_one_byte_multiple_extension() -> Result<()> {
             ..Default::default()
         },
         payload: raw_pkt[28..].into(),
+        ..Default::default()
     };
 
     let dst_data = p.marshal()?;
@@ -441,6 +446,7 @@ fn test_rfc_8285_two_byte_extension() -> Result<()> {
             ..Default::default()
         },
         payload: raw_pkt.slice(44..),
+        ..Default::default()
     };
 
     let dst_data = p.marshal()?;
@@ -556,6 +562,7 @@ fn test_rfc8285_two_byte_multiple_extension_with_large_extension() -> Result<()>
             ..Default::default()
         },
         payload: raw_pkt.slice(40..),
+        ..Default::default()
     };
 
     let dst_data = p.marshal()?;
@@ -1249,3 +1256,210 @@ fn test_round_trip() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_padding_size() -> Result<()> {
+    let p = Packet {
+        header: Header {
+            padding: true,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[1, 2, 3, 4]),
+        padding_size: 9,
+    };
+    let raw = p.marshal()?;
+    assert_eq!(raw.len(), p.marshal_size());
+    assert_eq!(raw.len(), 12 + 4 + 9);
+    assert_eq!(&raw[16..], &[0, 0, 0, 0, 0, 0, 0, 0, 9]);
+
+    // without a padding size the payload is padded to 4 bytes
+    let p = Packet {
+        padding_size: 0,
+        ..p
+    };
+    assert_eq!(p.marshal()?.len(), 12 + 4 + 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_padding_size_round_trip() -> Result<()> {
+    let mut p = Packet {
+        header: Header {
+            version: 2,
+            ssrc: 0x1234ABCD,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[1, 2, 3]),
+        ..Default::default()
+    };
+    p.set_padding_size(200)?;
+    assert!(p.header.padding);
+
+    let raw = p.marshal()?;
+    assert_eq!(raw.len(), 12 + 3 + 200);
+
+    let parsed = Packet::unmarshal(&mut raw.clone())?;
+    assert_eq!(parsed.padding_size, 200);
+    assert_eq!(parsed, p);
+    assert_eq!(parsed.marshal()?, raw);
+
+    assert_eq!(Err(Error::ErrPaddingSizeInvalid(0)), p.set_padding_size(0));
+    assert_eq!(
+        Err(Error::ErrPaddingSizeInvalid(256)),
+        p.set_padding_size(256)
+    );
+    assert_eq!(p.padding_size, 200);
+
+    Ok(())
+}
+
+#[test]
+fn test_unmarshal_strict() -> Result<()> {
+    let cases: Vec<(&str, Bytes, Error)> = vec![
+        (
+            "Version",
+            Bytes::from_static(&[
+                0x40, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+            ]),
+            Error::ErrFieldInvalid(0, "version"),
+        ),
+        (
+            "MissingCSRC",
+            Bytes::from_static(&[
+                0x81, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+            ]),
+            Error::ErrFieldTruncated(12, "csrc"),
+        ),
+        (
+            "MissingExtensionData",
+            Bytes::from_static(&[
+                0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0xBE, 0xDE, 0x00, 0x03, // specified to have 3 extensions, but actually not
+            ]),
+            Error::ErrFieldTruncated(14, "extension length"),
+        ),
+        (
+            "ReservedExtensionId",
+            Bytes::from_static(&[
+                0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0xBE, 0xDE, 0x00, 0x01, // one byte extensions
+                0x10, 0xAA, 0xF0, 0x00, // id 1 then reserved id 15
+            ]),
+            Error::ErrFieldInvalid(18, "extension id"),
+        ),
+        (
+            "ExtensionElementOverrun",
+            Bytes::from_static(&[
+                0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0xBE, 0xDE, 0x00, 0x01, // one byte extensions
+                0x00, 0x15, 0xAA, 0xBB, // id 1 of 6 bytes in a 4 bytes block
+                0xCC, 0xDD, 0xEE, 0xFF, // payload
+            ]),
+            Error::ErrFieldTruncated(17, "extension element"),
+        ),
+        (
+            "ZeroPadding",
+            Bytes::from_static(&[
+                0xa0, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0xAA, 0x00, // padding length 0
+            ]),
+            Error::ErrFieldInvalid(13, "padding length"),
+        ),
+        (
+            "PaddingOverrun",
+            Bytes::from_static(&[
+                0xa0, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0xAA, 0x05, // padding length larger than the payload
+            ]),
+            Error::ErrFieldInvalid(13, "padding length"),
+        ),
+    ];
+
+    for (name, raw, err) in cases {
+        let result = Packet::unmarshal_with_mode(&mut raw.clone(), ParseMode::Strict);
+        assert_eq!(err, result.unwrap_err(), "strict {}", name);
+    }
+
+    // lenient mode accepts a reserved id and an overrunning element as before
+    let raw = Bytes::from_static(&[
+        0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+        0x1c, 0x64, 0x27, 0x82, // SSRC
+        0xBE, 0xDE, 0x00, 0x01, // one byte extensions
+        0x00, 0x15, 0xAA, 0xBB, // id 1 of 6 bytes in a 4 bytes block
+        0xCC, 0xDD, 0xEE, 0xFF, // payload
+    ]);
+    let p = Packet::unmarshal_with_mode(&mut raw.clone(), ParseMode::Lenient)?;
+    assert_eq!(
+        p.header.get_extension(1),
+        Some(Bytes::from_static(&[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]))
+    );
+
+    // an element overrunning the packet is an error instead of a panic
+    let raw = Bytes::from_static(&[
+        0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+        0x1c, 0x64, 0x27, 0x82, // SSRC
+        0xBE, 0xDE, 0x00, 0x01, // one byte extensions
+        0x00, 0x15, 0xAA, 0xBB, // id 1 of 6 bytes in a 4 bytes block
+    ]);
+    let result = Packet::unmarshal(&mut raw.clone());
+    assert_eq!(
+        Error::ErrHeaderSizeInsufficientForExtension,
+        result.unwrap_err()
+    );
+
+    // a valid packet parses the same in both modes
+    let raw = Bytes::from_static(&[
+        0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, 0x1c, 0x64, 0x27, 0x82, 0xBE, 0xDE, 0x00,
+        0x01, 0x50, 0xAA, 0x00, 0x00, 0x98, 0x36, 0xbe, 0x88, 0x9e,
+    ]);
+    assert_eq!(
+        Packet::unmarshal_with_mode(&mut raw.clone(), ParseMode::Strict)?,
+        Packet::unmarshal(&mut raw.clone())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_padding_length_round_trip() -> Result<()> {
+    for padding_len in [1u8, 3, 4, 5, 255] {
+        let mut raw = vec![
+            0xA0, 0x60, 0x00, 0x01, 0, 0, 0, 1, 0x12, 0x34, 0xAB, 0xCD, 0x98,
+        ];
+        raw.extend(vec![0u8; padding_len as usize - 1]);
+        raw.push(padding_len);
+        let raw = Bytes::from(raw);
+
+        for mode in [ParseMode::Lenient, ParseMode::Strict] {
+            let p = Packet::unmarshal_with_mode(&mut raw.clone(), mode)?;
+            assert_eq!(p.payload, Bytes::from_static(&[0x98]));
+            assert_eq!(p.padding_size, padding_len);
+            assert_eq!(p.marshal()?, raw);
+        }
+    }
+
+    // a padding length of 0 is kept in the payload in lenient mode and rejected
+    // in strict mode
+    let raw = Bytes::from_static(&[
+        0xA0, 0x60, 0x00, 0x01, 0, 0, 0, 1, 0x12, 0x34, 0xAB, 0xCD, 0x98, 0x00,
+    ]);
+    let p = Packet::unmarshal_with_mode(&mut raw.clone(), ParseMode::Lenient)?;
+    assert!(p.header.padding);
+    assert_eq!(p.payload, Bytes::from_static(&[0x98, 0x00]));
+    assert_eq!(p.padding_size, 0);
+    assert_eq!(Packet::unmarshal(&mut raw.clone())?, p);
+    match Packet::unmarshal_with_mode(&mut raw.clone(), ParseMode::Strict) {
+        Err(err) => assert_eq!(Error::ErrFieldInvalid(13, "padding length"), err),
+        Ok(_) => panic!("expected a padding length error"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod packet_history_test;
+
+use crate::packet::Packet;
+use crate::sequence::Sequencer;
+
+use bytes::{BufMut, BytesMut};
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// StoredPacket is a sent packet with its retransmission state
+#[derive(Debug, Clone)]
+struct StoredPacket {
+    packet: Packet,
+    send_time: SystemTime,
+    retransmit_count: u32,
+    last_resend_time: Option<SystemTime>,
+}
+
+/// Rtx holds the RTX stream retransmissions are sent on, see RFC 4588
+#[derive(Debug, Clone)]
+struct Rtx {
+    ssrc: u32,
+    payload_type: u8,
+    sequencer: Box<dyn Sequencer + Send + Sync>,
+}
+
+/// PacketHistory keeps the recently sent packets of a stream to answer NACKs.
+/// It holds the packets of the last max_packets sequence numbers, sent no
+/// longer than max_age ago.
+#[derive(Debug, Clone)]
+pub struct PacketHistory {
+    max_packets: usize,
+    max_age: Duration,
+    min_resend_interval: Duration,
+    rtx: Option<Rtx>,
+
+    /// packets indexed by their sequence number offset from first_sequence_number,
+    /// None for sequence numbers which were not stored
+    packets: VecDeque<Option<StoredPacket>>,
+    first_sequence_number: u16,
+}
+
+impl PacketHistory {
+    pub fn new(max_packets: usize, max_age: Duration) -> Self {
+        PacketHistory {
+            max_packets,
+            max_age,
+            min_resend_interval: Duration::default(),
+            rtx: None,
+            packets: VecDeque::new(),
+            first_sequence_number: 0,
+        }
+    }
+
+    /// set_rtx wraps the resent packets as RTX packets with the given SSRC,
+    /// payload type and sequence numbers
+    pub fn set_rtx(
+        &mut self,
+        ssrc: u32,
+        payload_type: u8,
+        sequencer: Box<dyn Sequencer + Send + Sync>,
+    ) {
+        self.rtx = Some(Rtx {
+            ssrc,
+            payload_type,
+            sequencer,
+        });
+    }
+
+    /// set_min_resend_interval sets the time to wait before resending a packet
+    /// again, e.g. the round trip time
+    pub fn set_min_resend_interval(&mut self, interval: Duration) {
+        self.min_resend_interval = interval;
+    }
+
+    /// len returns the number of stored packets
+    pub fn len(&self) -> usize {
+        self.packets.iter().filter(|p| p.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packets.iter().all(|p| p.is_none())
+    }
+
+    /// put stores a packet sent at send_time, evicting the oldest packets past
+    /// the size and time limits. Packets older than the stored ones are ignored.
+    pub fn put(&mut self, packet: Packet, send_time: SystemTime) {
+        if self.max_packets == 0 {
+            return;
+        }
+
+        let sequence_number = packet.header.sequence_number;
+        if self.packets.is_empty() {
+            self.first_sequence_number = sequence_number;
+        }
+        let offset = sequence_number.wrapping_sub(self.first_sequence_number);
+        if offset >= 0x8000 {
+            return;
+        }
+
+        let offset = offset as usize;
+        if offset >= self.packets.len() + self.max_packets {
+            // too far ahead, none of the stored packets would be kept
+            self.packets.clear();
+            self.first_sequence_number = sequence_number;
+            self.packets.push_back(None);
+        } else {
+            while self.packets.len() <= offset {
+                self.packets.push_back(None);
+            }
+        }
+        let index = sequence_number.wrapping_sub(self.first_sequence_number) as usize;
+        self.packets[index] = Some(StoredPacket {
+            packet,
+            send_time,
+            retransmit_count: 0,
+            last_resend_time: None,
+        });
+
+        self.evict(send_time);
+    }
+
+    /// evict drops the packets past the size limit and those sent before the
+    /// time limit, as of now
+    fn evict(&mut self, now: SystemTime) {
+        while let Some(front) = self.packets.front() {
+            let expired = match front {
+                Some(stored) => {
+                    self.packets.len() > self.max_packets
+                        || matches!(now.duration_since(stored.send_time), Ok(age) if age > self.max_age)
+                }
+                None => true,
+            };
+            if !expired {
+                break;
+            }
+            self.packets.pop_front();
+            self.first_sequence_number = self.first_sequence_number.wrapping_add(1);
+        }
+    }
+
+    fn stored(&self, sequence_number: u16) -> Option<&StoredPacket> {
+        let index = sequence_number.wrapping_sub(self.first_sequence_number) as usize;
+        self.packets.get(index)?.as_ref()
+    }
+
+    /// get returns the stored packet with the sequence number
+    pub fn get(&self, sequence_number: u16) -> Option<&Packet> {
+        self.stored(sequence_number).map(|stored| &stored.packet)
+    }
+
+    /// retransmit_count returns how many times the packet has been resent
+    pub fn retransmit_count(&self, sequence_number: u16) -> Option<u32> {
+        self.stored(sequence_number)
+            .map(|stored| stored.retransmit_count)
+    }
+
+    /// last_resend_time returns when the packet was last resent
+    pub fn last_resend_time(&self, sequence_number: u16) -> Option<SystemTime> {
+        self.stored(sequence_number)?.last_resend_time
+    }
+
+    /// resend returns the packets to retransmit at now for the requested
+    /// sequence numbers, wrapped as RTX packets if enabled. Packets which are
+    /// not stored anymore, older than the time limit, or already resent within
+    /// the minimum resend interval are left out.
+    pub fn resend(&mut self, sequence_numbers: &[u16], now: SystemTime) -> Vec<Packet> {
+        let mut packets = vec![];
+        for &sequence_number in sequence_numbers {
+            let index = sequence_number.wrapping_sub(self.first_sequence_number) as usize;
+            let stored = match self.packets.get_mut(index) {
+                Some(Some(stored)) => stored,
+                _ => continue,
+            };
+            let elapsed = |t: SystemTime| now.duration_since(t).unwrap_or_default();
+            if elapsed(stored.send_time) > self.max_age {
+                continue;
+            }
+            if let Some(last_resend_time) = stored.last_resend_time {
+                if elapsed(last_resend_time) < self.min_resend_interval {
+                    continue;
+                }
+            }
+
+            stored.retransmit_count += 1;
+            stored.last_resend_time = Some(now);
+            packets.push(match &self.rtx {
+                Some(rtx) => wrap_rtx(&stored.packet, rtx),
+                None => stored.packet.clone(),
+            });
+        }
+
+        packets
+    }
+}
+
+/// wrap_rtx builds the RTX packet of a packet, prefixing its payload with the
+/// original sequence number
+fn wrap_rtx(packet: &Packet, rtx: &Rtx) -> Packet {
+    let mut payload = BytesMut::with_capacity(2 + packet.payload.len());
+    payload.put_u16(packet.header.sequence_number);
+    payload.put_slice(&packet.payload);
+
+    let mut rtx_packet = packet.clone();
+    rtx_packet.header.ssrc = rtx.ssrc;
+    rtx_packet.header.payload_type = rtx.payload_type;
+    rtx_packet.header.sequence_number = rtx.sequencer.next_sequence_number();
+    rtx_packet.payload = payload.freeze();
+    rtx_packet
+}
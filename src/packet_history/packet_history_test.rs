@@ -0,0 +1,101 @@
+use super::*;
+use crate::sequence::new_fixed_sequencer;
+use crate::test_util::{at, packet};
+
+use bytes::Bytes;
+
+const SSRC: u32 = 0x1234;
+const PAYLOAD: &[u8] = &[0xAA, 0xBB];
+
+#[test]
+fn test_packet_history_limits() {
+    let mut history = PacketHistory::new(3, Duration::from_secs(1));
+    assert!(history.is_empty());
+
+    for sequence_number in 65534..=65535 {
+        history.put(packet(SSRC, sequence_number, 3000, PAYLOAD), at(0));
+    }
+    // a gap in the sequence numbers, e.g. padding which is not stored
+    history.put(packet(SSRC, 1, 3000, PAYLOAD), at(10));
+    assert_eq!(history.get(1), Some(&packet(SSRC, 1, 3000, PAYLOAD)));
+    assert_eq!(history.get(0), None);
+
+    // the oldest packets are evicted past the size limit
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(65534), None);
+    assert!(history.get(65535).is_some());
+    history.put(packet(SSRC, 2, 3000, PAYLOAD), at(20));
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(65535), None);
+    assert!(history.get(2).is_some());
+
+    // and past the time limit
+    history.put(packet(SSRC, 3, 3000, PAYLOAD), at(1015));
+    assert_eq!(history.len(), 2);
+    assert!(history.get(1).is_none());
+
+    // older packets are ignored
+    history.put(packet(SSRC, 1, 3000, PAYLOAD), at(1020));
+    assert!(history.get(1).is_none());
+
+    // a packet far ahead restarts the history
+    history.put(packet(SSRC, 30000, 3000, PAYLOAD), at(1030));
+    assert_eq!(history.len(), 1);
+    assert!(history.get(30000).is_some());
+}
+
+#[test]
+fn test_packet_history_resend() {
+    let mut history = PacketHistory::new(100, Duration::from_secs(1));
+    history.set_min_resend_interval(Duration::from_millis(100));
+    for sequence_number in 10..15 {
+        history.put(packet(SSRC, sequence_number, 3000, PAYLOAD), at(0));
+    }
+
+    let packets = history.resend(&[11, 13, 20], at(10));
+    assert_eq!(
+        packets,
+        vec![
+            packet(SSRC, 11, 3000, PAYLOAD),
+            packet(SSRC, 13, 3000, PAYLOAD)
+        ]
+    );
+    assert_eq!(history.retransmit_count(11), Some(1));
+    assert_eq!(history.last_resend_time(11), Some(at(10)));
+    assert_eq!(history.retransmit_count(12), Some(0));
+    assert_eq!(history.last_resend_time(12), None);
+
+    // resending again is throttled
+    let packets = history.resend(&[11, 12], at(50));
+    assert_eq!(packets, vec![packet(SSRC, 12, 3000, PAYLOAD)]);
+    let packets = history.resend(&[11], at(110));
+    assert_eq!(packets, vec![packet(SSRC, 11, 3000, PAYLOAD)]);
+    assert_eq!(history.retransmit_count(11), Some(2));
+
+    // packets older than the time limit are not resent
+    assert!(history.resend(&[14], at(1001)).is_empty());
+}
+
+#[test]
+fn test_packet_history_rtx() {
+    let mut history = PacketHistory::new(100, Duration::from_secs(1));
+    history.set_rtx(0x5678, 97, Box::new(new_fixed_sequencer(500)));
+    history.put(packet(SSRC, 0x0102, 3000, PAYLOAD), at(0));
+    history.put(packet(SSRC, 0x0103, 3000, PAYLOAD), at(0));
+
+    let packets = history.resend(&[0x0103, 0x0102], at(10));
+    assert_eq!(packets.len(), 2);
+    for (rtx_packet, (sequence_number, osn)) in packets.iter().zip([(500, 0x03), (501, 0x02)]) {
+        assert_eq!(rtx_packet.header.ssrc, 0x5678);
+        assert_eq!(rtx_packet.header.payload_type, 97);
+        assert_eq!(rtx_packet.header.sequence_number, sequence_number);
+        assert_eq!(rtx_packet.header.timestamp, 3000);
+        assert_eq!(rtx_packet.payload, Bytes::from(vec![0x01, osn, 0xAA, 0xBB]));
+    }
+
+    // the stored packet is unchanged
+    assert_eq!(
+        history.get(0x0102),
+        Some(&packet(SSRC, 0x0102, 3000, PAYLOAD))
+    );
+}
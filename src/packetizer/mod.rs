@@ -1,17 +1,22 @@
 #[cfg(test)]
 mod packetizer_test;
 
-use crate::error::Result;
-use crate::{extension::abs_send_time_extension::*, header::*, packet::*, sequence::*};
+use crate::error::{Error, Result};
+use crate::{
+    extension::{abs_send_time_extension::*, transport_cc_extension::*},
+    header::*,
+    packet::*,
+    sequence::*,
+    stats::sender_stats::SenderStats,
+};
 use util::marshal::{Marshal, MarshalSize};
 
-use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 /// Payloader payloads a byte array for use as rtp.Packet payloads
 pub trait Payloader: fmt::Debug {
@@ -25,11 +30,92 @@ impl Clone for Box<dyn Payloader + Send + Sync> {
     }
 }
 
-/// Packetizer packetizes a payload
-#[async_trait]
+/// PacketInfo describes a packet produced by a Packetizer
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PacketInfo {
+    /// index of the packet among the packets of the payload
+    pub index: usize,
+    /// first packet of the payload
+    pub is_first: bool,
+    /// last packet of the payload
+    pub is_last: bool,
+    /// number of samples of the payload, as passed to packetize, or 0 for
+    /// packetize_at
+    pub samples: u32,
+}
+
+/// ExtensionProvider sets header extensions on the packets produced by a Packetizer
+pub trait ExtensionProvider: fmt::Debug {
+    fn set_extensions(&mut self, header: &mut Header, info: &PacketInfo) -> Result<()>;
+    /// max_extension_sizes returns the id and maximum payload size of each
+    /// extension set_extensions may set, so the packetizer can keep packets
+    /// within the MTU and pick the extension profile
+    fn max_extension_sizes(&self) -> Vec<(u8, usize)>;
+    fn clone_to(&self) -> Box<dyn ExtensionProvider + Send + Sync>;
+}
+
+impl Clone for Box<dyn ExtensionProvider + Send + Sync> {
+    fn clone(&self) -> Box<dyn ExtensionProvider + Send + Sync> {
+        self.clone_to()
+    }
+}
+
+/// FixedExtensionProvider sets the same extension payload on packets, e.g. the
+/// MID or RID of a stream
+#[derive(Debug, Clone)]
+pub struct FixedExtensionProvider {
+    pub id: u8,
+    pub payload: Bytes,
+    /// only set the extension on the first packet of each payload
+    pub first_packet_only: bool,
+}
+
+impl ExtensionProvider for FixedExtensionProvider {
+    fn set_extensions(&mut self, header: &mut Header, info: &PacketInfo) -> Result<()> {
+        if !self.first_packet_only || info.is_first {
+            header.set_extension(self.id, self.payload.clone())?;
+        }
+        Ok(())
+    }
+
+    fn max_extension_sizes(&self) -> Vec<(u8, usize)> {
+        vec![(self.id, self.payload.len())]
+    }
+
+    fn clone_to(&self) -> Box<dyn ExtensionProvider + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// Packetizer packetizes a payload. It is implemented by the packetizers of
+/// new_packetizer and PacketizerBuilder, see CHANGELOG.md for migrating custom
+/// implementations from the async trait.
 pub trait Packetizer: fmt::Debug {
     fn enable_abs_send_time(&mut self, value: u8);
-    async fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>>;
+    fn abs_send_time(&self) -> u8;
+    fn enable_transport_cc(&mut self, value: u8, sequencer: Box<dyn Sequencer + Send + Sync>);
+    fn add_extension_provider(&mut self, provider: Box<dyn ExtensionProvider + Send + Sync>);
+    fn set_csrc(&mut self, csrc: Vec<u32>);
+    fn set_srtp_overhead(&mut self, overhead: usize);
+    fn set_clock(&mut self, clock: Arc<dyn Clock + Send + Sync>);
+    fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>>;
+    fn packetize_with_send_time(
+        &mut self,
+        payload: &Bytes,
+        samples: u32,
+        send_time: SystemTime,
+    ) -> Result<Vec<Packet>>;
+    fn packetize_at(&mut self, payload: &Bytes, presentation_time: Duration)
+        -> Result<Vec<Packet>>;
+    fn timestamp_mapping(&self) -> Option<TimestampMapping>;
+    fn sender_stats(&self) -> SenderStats;
+    fn generate_padding(&mut self, padding_size: usize) -> Result<Packet>;
+    fn ssrc(&self) -> u32;
+    fn set_ssrc(&mut self, ssrc: u32);
+    fn payload_type(&self) -> u8;
+    fn set_payload_type(&mut self, payload_type: u8);
+    fn timestamp(&self) -> u32;
+    fn set_timestamp(&mut self, timestamp: u32);
     fn skip_samples(&mut self, skipped_samples: u32);
     fn clone_to(&self) -> Box<dyn Packetizer + Send + Sync>;
 }
@@ -56,6 +142,54 @@ pub trait Depacketizer {
 
 //TODO: SystemTime vs Instant?
 // non-monotonic clock vs monotonically non-decreasing clock
+/// Clock provides current SystemTime
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// SystemClock is a Clock reading SystemTime::now
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// FixedClock always returns the same time
+#[derive(Debug, Copy, Clone)]
+struct FixedClock(SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// TimestampMapping pairs a wallclock time with the RTP timestamp of the same
+/// instant, as carried by RTCP sender reports. It is derived from the sender
+/// stats, see SenderStats::sender_info.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TimestampMapping {
+    /// wallclock time in NTP format
+    pub ntp_time: u64,
+    pub rtp_time: u32,
+}
+
+/// TimestampBase anchors presentation times to RTP timestamps
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct TimestampBase {
+    pub(crate) presentation_time: Duration,
+    pub(crate) rtp_time: u32,
+}
+
+/// ticks_between returns the number of clock_rate ticks from one point in time to
+/// another, negative when to is before from
+fn ticks_between(from: i128, to: i128, clock_rate: u32) -> i128 {
+    ((to - from) * clock_rate as i128).div_euclid(1_000_000_000)
+}
+
 /// FnTimeGen provides current SystemTime
 pub type FnTimeGen =
     Arc<dyn (Fn() -> Pin<Box<dyn Future<Output = SystemTime> + Send + 'static>>) + Send + Sync>;
@@ -65,12 +199,19 @@ pub(crate) struct PacketizerImpl {
     pub(crate) mtu: usize,
     pub(crate) payload_type: u8,
     pub(crate) ssrc: u32,
+    pub(crate) csrc: Vec<u32>,
     pub(crate) payloader: Box<dyn Payloader + Send + Sync>,
     pub(crate) sequencer: Box<dyn Sequencer + Send + Sync>,
     pub(crate) timestamp: u32,
+    pub(crate) timestamp_base: Option<TimestampBase>,
     pub(crate) clock_rate: u32,
     pub(crate) abs_send_time: u8, //http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time
-    pub(crate) time_gen: Option<FnTimeGen>,
+    pub(crate) transport_cc: u8, //http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01
+    pub(crate) transport_cc_sequencer: Option<Box<dyn Sequencer + Send + Sync>>,
+    pub(crate) extension_providers: Vec<Box<dyn ExtensionProvider + Send + Sync>>,
+    pub(crate) srtp_overhead: usize,
+    pub(crate) sender_stats: SenderStats,
+    pub(crate) clock: Arc<dyn Clock + Send + Sync>,
 }
 
 impl fmt::Debug for PacketizerImpl {
@@ -79,9 +220,15 @@ impl fmt::Debug for PacketizerImpl {
             .field("mtu", &self.mtu)
             .field("payload_type", &self.payload_type)
             .field("ssrc", &self.ssrc)
+            .field("csrc", &self.csrc)
             .field("timestamp", &self.timestamp)
+            .field("timestamp_base", &self.timestamp_base)
             .field("clock_rate", &self.clock_rate)
             .field("abs_send_time", &self.abs_send_time)
+            .field("transport_cc", &self.transport_cc)
+            .field("extension_providers", &self.extension_providers)
+            .field("srtp_overhead", &self.srtp_overhead)
+            .field("sender_stats", &self.sender_stats)
             .finish()
     }
 }
@@ -94,67 +241,449 @@ pub fn new_packetizer(
     sequencer: Box<dyn Sequencer + Send + Sync>,
     clock_rate: u32,
 ) -> impl Packetizer {
-    PacketizerImpl {
-        mtu,
-        payload_type,
-        ssrc,
-        payloader,
-        sequencer,
-        timestamp: rand::random::<u32>(),
-        clock_rate,
-        abs_send_time: 0,
-        time_gen: None,
+    PacketizerBuilder::new(payload_type, clock_rate, payloader)
+        .with_mtu(mtu)
+        .with_ssrc(ssrc)
+        .with_sequencer(sequencer)
+        .build()
+}
+
+/// PacketizerBuilder configures and builds a Packetizer
+pub struct PacketizerBuilder {
+    packetizer: PacketizerImpl,
+}
+
+impl PacketizerBuilder {
+    /// new creates a builder with a 1200 bytes MTU, a random SSRC, random
+    /// sequence numbers and a random initial timestamp
+    pub fn new(
+        payload_type: u8,
+        clock_rate: u32,
+        payloader: Box<dyn Payloader + Send + Sync>,
+    ) -> Self {
+        PacketizerBuilder {
+            packetizer: PacketizerImpl {
+                mtu: 1200,
+                payload_type,
+                ssrc: rand::random::<u32>(),
+                csrc: vec![],
+                payloader,
+                sequencer: Box::new(new_random_sequencer()),
+                timestamp: rand::random::<u32>(),
+                timestamp_base: None,
+                clock_rate,
+                abs_send_time: 0,
+                transport_cc: 0,
+                transport_cc_sequencer: None,
+                extension_providers: vec![],
+                srtp_overhead: 0,
+                sender_stats: SenderStats::new(clock_rate),
+                clock: Arc::new(SystemClock),
+            },
+        }
+    }
+
+    pub fn with_mtu(mut self, mtu: usize) -> Self {
+        self.packetizer.mtu = mtu;
+        self
+    }
+
+    pub fn with_ssrc(mut self, ssrc: u32) -> Self {
+        self.packetizer.ssrc = ssrc;
+        self
+    }
+
+    pub fn with_csrc(mut self, csrc: Vec<u32>) -> Self {
+        self.packetizer.csrc = csrc;
+        self
+    }
+
+    pub fn with_sequencer(mut self, sequencer: Box<dyn Sequencer + Send + Sync>) -> Self {
+        self.packetizer.sequencer = sequencer;
+        self
+    }
+
+    pub fn with_initial_timestamp(mut self, timestamp: u32) -> Self {
+        self.packetizer.timestamp = timestamp;
+        self
+    }
+
+    /// with_clock sets the time source of the abs-send-time extension, the
+    /// sender stats and the NTP/RTP timestamp mapping
+    pub fn with_clock(mut self, clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        self.packetizer.clock = clock;
+        self
+    }
+
+    pub fn with_abs_send_time(mut self, value: u8) -> Self {
+        self.packetizer.enable_abs_send_time(value);
+        self
+    }
+
+    pub fn with_transport_cc(
+        mut self,
+        value: u8,
+        sequencer: Box<dyn Sequencer + Send + Sync>,
+    ) -> Self {
+        self.packetizer.enable_transport_cc(value, sequencer);
+        self
+    }
+
+    pub fn with_extension_provider(
+        mut self,
+        provider: Box<dyn ExtensionProvider + Send + Sync>,
+    ) -> Self {
+        self.packetizer.add_extension_provider(provider);
+        self
+    }
+
+    pub fn with_srtp_overhead(mut self, overhead: usize) -> Self {
+        self.packetizer.srtp_overhead = overhead;
+        self
+    }
+
+    pub fn build(self) -> impl Packetizer {
+        self.packetizer
     }
 }
 
-#[async_trait]
-impl Packetizer for PacketizerImpl {
-    fn enable_abs_send_time(&mut self, value: u8) {
-        self.abs_send_time = value
+impl PacketizerImpl {
+    /// extension_sizes returns the id and maximum payload size of every
+    /// extension the packetizer may set
+    fn extension_sizes(&self) -> Vec<(u8, usize)> {
+        let mut extension_sizes = vec![];
+        if self.abs_send_time != 0 {
+            extension_sizes.push((self.abs_send_time, ABS_SEND_TIME_EXTENSION_SIZE));
+        }
+        if self.transport_cc != 0 && self.transport_cc_sequencer.is_some() {
+            extension_sizes.push((self.transport_cc, TRANSPORT_CC_EXTENSION_SIZE));
+        }
+        for provider in &self.extension_providers {
+            extension_sizes.extend(provider.max_extension_sizes());
+        }
+        extension_sizes
+    }
+
+    /// extension_profile returns the one-byte profile if every extension fits
+    /// it, the two-byte profile otherwise
+    fn extension_profile(extension_sizes: &[(u8, usize)]) -> u16 {
+        if extension_sizes
+            .iter()
+            .all(|(id, size)| (1..=14).contains(id) && *size <= 16)
+        {
+            EXTENSION_PROFILE_ONE_BYTE
+        } else {
+            EXTENSION_PROFILE_TWO_BYTE
+        }
+    }
+
+    /// packet_overhead returns the size of everything but the payload in the
+    /// largest packet the packetizer can produce with the given extensions
+    fn packet_overhead(&self, extension_sizes: &[(u8, usize)]) -> usize {
+        let header = Header {
+            csrc: self.csrc.clone(),
+            extension: !extension_sizes.is_empty(),
+            extension_profile: PacketizerImpl::extension_profile(extension_sizes),
+            extensions: extension_sizes
+                .iter()
+                .map(|&(id, size)| Extension {
+                    id,
+                    payload: Bytes::from(vec![0u8; size]),
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        header.marshal_size() + self.srtp_overhead
+    }
+
+    /// new_header returns the header of the next packet. The two-byte extension
+    /// profile is set up front, as setting the first extension picks the
+    /// profile from its payload size only.
+    fn new_header(&self, marker: bool, extension_profile: u16) -> Header {
+        Header {
+            version: 2,
+            extension: extension_profile == EXTENSION_PROFILE_TWO_BYTE,
+            extension_profile,
+            marker,
+            payload_type: self.payload_type,
+            sequence_number: self.sequencer.next_sequence_number(),
+            timestamp: self.timestamp,
+            ssrc: self.ssrc,
+            csrc: self.csrc.clone(),
+            ..Default::default()
+        }
     }
 
-    async fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>> {
-        let payloads = self.payloader.payload(self.mtu - 12, payload)?;
+    /// packetize_payload packetizes a payload sent at send_time with the current
+    /// timestamp
+    fn packetize_payload(
+        &mut self,
+        payload: &Bytes,
+        samples: u32,
+        send_time: SystemTime,
+    ) -> Result<Vec<Packet>> {
+        let extension_sizes = self.extension_sizes();
+        let extension_profile = PacketizerImpl::extension_profile(&extension_sizes);
+        let overhead = self.packet_overhead(&extension_sizes);
+        if overhead >= self.mtu {
+            return Err(Error::ErrMtuTooSmall(self.mtu, overhead));
+        }
+        let payloads = self.payloader.payload(self.mtu - overhead, payload)?;
         let payloads_len = payloads.len();
         let mut packets = Vec::with_capacity(payloads_len);
         for (i, payload) in payloads.into_iter().enumerate() {
             packets.push(Packet {
-                header: Header {
-                    version: 2,
-                    padding: false,
-                    extension: false,
-                    marker: i == payloads_len - 1,
-                    payload_type: self.payload_type,
-                    sequence_number: self.sequencer.next_sequence_number(),
-                    timestamp: self.timestamp, //TODO: Figure out how to do timestamps
-                    ssrc: self.ssrc,
-                    ..Default::default()
-                },
+                header: self.new_header(i == payloads_len - 1, extension_profile),
                 payload,
+                ..Default::default()
             });
         }
 
-        self.timestamp = self.timestamp.wrapping_add(samples);
-
-        if payloads_len != 0 && self.abs_send_time != 0 {
-            let st = if let Some(fn_time_gen) = &self.time_gen {
-                fn_time_gen().await
-            } else {
-                SystemTime::now()
+        for (i, packet) in packets.iter_mut().enumerate() {
+            let info = PacketInfo {
+                index: i,
+                is_first: i == 0,
+                is_last: i == payloads_len - 1,
+                samples,
             };
-            let send_time = AbsSendTimeExtension::new(st);
+            for provider in &mut self.extension_providers {
+                provider.set_extensions(&mut packet.header, &info)?;
+            }
+        }
+
+        self.set_send_extensions(&mut packets, send_time)?;
+
+        Ok(packets)
+    }
+
+    /// set_send_extensions sets the transport-cc extension on every packet and
+    /// the abs-send-time extension on the last packet, and counts the packets in
+    /// the sender stats, for packets sent at now
+    fn set_send_extensions(&mut self, packets: &mut [Packet], now: SystemTime) -> Result<()> {
+        for packet in packets.iter() {
+            self.sender_stats.update(packet, now);
+        }
+
+        if let (true, Some(sequencer)) = (self.transport_cc != 0, &self.transport_cc_sequencer) {
+            //apply http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01
+            for packet in packets.iter_mut() {
+                let transport_cc = TransportCcExtension {
+                    transport_sequence: sequencer.next_sequence_number(),
+                };
+                let mut raw = BytesMut::with_capacity(transport_cc.marshal_size());
+                raw.resize(transport_cc.marshal_size(), 0);
+                let _ = transport_cc.marshal_to(&mut raw)?;
+                packet
+                    .header
+                    .set_extension(self.transport_cc, raw.freeze())?;
+            }
+        }
+
+        if let (true, Some(packet)) = (self.abs_send_time != 0, packets.last_mut()) {
+            let send_time = AbsSendTimeExtension::new(now);
             //apply http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time
             let mut raw = BytesMut::with_capacity(send_time.marshal_size());
             raw.resize(send_time.marshal_size(), 0);
             let _ = send_time.marshal_to(&mut raw)?;
-            packets[payloads_len - 1]
+            packet
                 .header
                 .set_extension(self.abs_send_time, raw.freeze())?;
         }
 
+        // drop the extension block preset by new_header if nothing was set
+        for packet in packets.iter_mut() {
+            if packet.header.extensions.is_empty() {
+                packet.header.extension = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Packetizer for PacketizerImpl {
+    fn enable_abs_send_time(&mut self, value: u8) {
+        self.abs_send_time = value
+    }
+
+    /// abs_send_time returns the id of the abs-send-time extension, 0 if disabled
+    fn abs_send_time(&self) -> u8 {
+        self.abs_send_time
+    }
+
+    /// enable_transport_cc stamps every packet with a transport-wide sequence number
+    /// taken from sequencer. Clones of a sequencer share their state, so the same
+    /// sequencer can be given to the packetizers of all streams of a transport.
+    fn enable_transport_cc(&mut self, value: u8, sequencer: Box<dyn Sequencer + Send + Sync>) {
+        self.transport_cc = value;
+        self.transport_cc_sequencer = Some(sequencer);
+    }
+
+    /// add_extension_provider registers a provider called for every packet, in
+    /// the order providers were added
+    fn add_extension_provider(&mut self, provider: Box<dyn ExtensionProvider + Send + Sync>) {
+        self.extension_providers.push(provider);
+    }
+
+    /// set_csrc sets the contributing sources of the following packets
+    fn set_csrc(&mut self, csrc: Vec<u32>) {
+        self.csrc = csrc;
+    }
+
+    /// set_srtp_overhead reserves room in every packet for the SRTP
+    /// authentication tag and MKI added after packetization
+    fn set_srtp_overhead(&mut self, overhead: usize) {
+        self.srtp_overhead = overhead;
+    }
+
+    /// set_clock sets the clock giving the send time of packets, used for the
+    /// abs-send-time extension and the sender stats, and the current time of
+    /// timestamp_mapping
+    fn set_clock(&mut self, clock: Arc<dyn Clock + Send + Sync>) {
+        self.clock = clock;
+    }
+
+    fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>> {
+        let now = self.clock.now();
+        self.packetize_with_send_time(payload, samples, now)
+    }
+
+    /// packetize_with_send_time packetizes a payload like packetize, using
+    /// send_time instead of the clock for the abs-send-time extension and the
+    /// sender stats
+    fn packetize_with_send_time(
+        &mut self,
+        payload: &Bytes,
+        samples: u32,
+        send_time: SystemTime,
+    ) -> Result<Vec<Packet>> {
+        let packets = self.packetize_payload(payload, samples, send_time)?;
+        self.timestamp = self.timestamp.wrapping_add(samples);
         Ok(packets)
     }
 
+    /// packetize_at packetizes a payload presented at presentation_time. The RTP
+    /// timestamp is derived from the presentation time relative to the first
+    /// payload packetized with packetize_at, which keeps the current timestamp.
+    fn packetize_at(
+        &mut self,
+        payload: &Bytes,
+        presentation_time: Duration,
+    ) -> Result<Vec<Packet>> {
+        let base = match self.timestamp_base {
+            Some(base) => base,
+            None => {
+                let base = TimestampBase {
+                    presentation_time,
+                    rtp_time: self.timestamp,
+                };
+                self.timestamp_base = Some(base);
+                base
+            }
+        };
+
+        let ticks = ticks_between(
+            base.presentation_time.as_nanos() as i128,
+            presentation_time.as_nanos() as i128,
+            self.clock_rate,
+        );
+        // truncating to u32 wraps the timestamp around
+        self.timestamp = base.rtp_time.wrapping_add(ticks as u32);
+
+        let now = self.clock.now();
+        self.packetize_payload(payload, 0, now)
+    }
+
+    /// timestamp_mapping returns the RTP timestamp of the current time of the
+    /// clock, extrapolated from the last packet sent like the sender stats
+    fn timestamp_mapping(&self) -> Option<TimestampMapping> {
+        let info = self.sender_stats.sender_info(self.clock.now())?;
+        Some(TimestampMapping {
+            ntp_time: info.ntp_time,
+            rtp_time: info.rtp_time,
+        })
+    }
+
+    /// generate_padding generates a padding-only packet for bandwidth probing,
+    /// with padding_size bytes of padding. It carries the current timestamp, the
+    /// next sequence number, the extensions of the providers and the
+    /// transport-cc and abs-send-time extensions. Padding which does not fit
+    /// the MTU along with the header and the SRTP overhead is an error.
+    fn generate_padding(&mut self, padding_size: usize) -> Result<Packet> {
+        let mut packet = Packet::default();
+        packet.set_padding_size(padding_size)?;
+
+        let extension_sizes = self.extension_sizes();
+        let packet_size = self.packet_overhead(&extension_sizes) + padding_size;
+        if packet_size > self.mtu {
+            return Err(Error::ErrMtuTooSmall(self.mtu, packet_size));
+        }
+
+        let extension_profile = PacketizerImpl::extension_profile(&extension_sizes);
+        let mut packets = [Packet {
+            header: Header {
+                padding: true,
+                ..self.new_header(false, extension_profile)
+            },
+            ..packet
+        }];
+        let info = PacketInfo {
+            index: 0,
+            is_first: true,
+            is_last: true,
+            samples: 0,
+        };
+        for provider in &mut self.extension_providers {
+            provider.set_extensions(&mut packets[0].header, &info)?;
+        }
+        let now = self.clock.now();
+        self.set_send_extensions(&mut packets, now)?;
+
+        let [packet] = packets;
+        Ok(packet)
+    }
+
+    /// sender_stats returns the packets and octets sent and the RTP timestamp
+    /// of the last packet, for RTCP sender reports
+    fn sender_stats(&self) -> SenderStats {
+        self.sender_stats
+    }
+
+    fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    /// set_ssrc changes the SSRC of the following packets, starting over the
+    /// sender stats of the new stream
+    fn set_ssrc(&mut self, ssrc: u32) {
+        self.ssrc = ssrc;
+        self.sender_stats = SenderStats::new(self.clock_rate);
+    }
+
+    fn payload_type(&self) -> u8 {
+        self.payload_type
+    }
+
+    /// set_payload_type changes the payload type of the following packets, e.g.
+    /// after renegotiation
+    fn set_payload_type(&mut self, payload_type: u8) {
+        self.payload_type = payload_type;
+    }
+
+    /// timestamp returns the RTP timestamp of the next packetized payload
+    fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// set_timestamp sets the RTP timestamp of the next packetized payload. It
+    /// drops the base of presentation times, the next packetize_at starts over
+    /// from this timestamp.
+    fn set_timestamp(&mut self, timestamp: u32) {
+        self.timestamp = timestamp;
+        self.timestamp_base = None;
+    }
+
     /// skip_samples causes a gap in sample count between Packetize requests so the
     /// RTP payloads produced have a gap in timestamps
     fn skip_samples(&mut self, skipped_samples: u32) {
@@ -165,3 +694,47 @@ impl Packetizer for PacketizerImpl {
         Box::new(self.clone())
     }
 }
+
+/// AsyncPacketizer packetizes with a Packetizer, generating the time of the
+/// abs-send-time extension with an async FnTimeGen
+#[derive(Clone)]
+pub struct AsyncPacketizer {
+    packetizer: Box<dyn Packetizer + Send + Sync>,
+    time_gen: Option<FnTimeGen>,
+}
+
+impl fmt::Debug for AsyncPacketizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncPacketizer")
+            .field("packetizer", &self.packetizer)
+            .finish()
+    }
+}
+
+impl AsyncPacketizer {
+    pub fn new(packetizer: Box<dyn Packetizer + Send + Sync>, time_gen: Option<FnTimeGen>) -> Self {
+        AsyncPacketizer {
+            packetizer,
+            time_gen,
+        }
+    }
+
+    /// packetizer returns the underlying Packetizer
+    pub fn packetizer(&mut self) -> &mut (dyn Packetizer + Send + Sync) {
+        self.packetizer.as_mut()
+    }
+
+    /// packetize packetizes a payload, generating the send time only if the
+    /// abs-send-time extension is enabled and the payload is not empty. The
+    /// clock of the underlying Packetizer is left as is.
+    pub async fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>> {
+        match &self.time_gen {
+            Some(fn_time_gen) if self.packetizer.abs_send_time() != 0 && !payload.is_empty() => {
+                let send_time = fn_time_gen().await;
+                self.packetizer
+                    .packetize_with_send_time(payload, samples, send_time)
+            }
+            _ => self.packetizer.packetize(payload, samples),
+        }
+    }
+}
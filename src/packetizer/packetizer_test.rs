@@ -4,16 +4,17 @@ use crate::error::Result;
 
 use chrono::prelude::*;
 use std::time::{Duration, UNIX_EPOCH};
+use util::marshal::{Marshal, Unmarshal};
 
-#[tokio::test]
-async fn test_packetizer() -> Result<()> {
+#[test]
+fn test_packetizer() -> Result<()> {
     let multiple_payload = Bytes::from_static(&[0; 128]);
     let g722 = Box::new(g7xx::G722Payloader {});
     let seq = Box::new(new_random_sequencer());
 
     //use the G722 payloader here, because it's very simple and all 0s is valid G722 data.
     let mut packetizer = new_packetizer(100, 98, 0x1234ABCD, g722, seq, 90000);
-    let packets = packetizer.packetize(&multiple_payload, 2000).await?;
+    let packets = packetizer.packetize(&multiple_payload, 2000)?;
 
     if packets.len() != 2 {
         let mut packet_lengths = String::new();
@@ -31,39 +32,44 @@ async fn test_packetizer() -> Result<()> {
     Ok(())
 }
 
-#[tokio::test]
-async fn test_packetizer_abs_send_time() -> Result<()> {
+fn test_time() -> SystemTime {
+    let loc = FixedOffset::west(5 * 60 * 60); // UTC-5
+    let t = loc.ymd(1985, 6, 23).and_hms_nano(4, 0, 0, 0);
+    UNIX_EPOCH
+        .checked_add(Duration::from_nanos(t.timestamp_nanos() as u64))
+        .unwrap_or(UNIX_EPOCH)
+}
+
+#[test]
+fn test_packetizer_abs_send_time() -> Result<()> {
     let g722 = Box::new(g7xx::G722Payloader {});
     let sequencer = Box::new(new_fixed_sequencer(1234));
 
-    let time_gen: Option<FnTimeGen> = Some(Arc::new(
-        || -> Pin<Box<dyn Future<Output = SystemTime> + Send + 'static>> {
-            Box::pin(async move {
-                let loc = FixedOffset::west(5 * 60 * 60); // UTC-5
-                let t = loc.ymd(1985, 6, 23).and_hms_nano(4, 0, 0, 0);
-                UNIX_EPOCH
-                    .checked_add(Duration::from_nanos(t.timestamp_nanos() as u64))
-                    .unwrap_or(UNIX_EPOCH)
-            })
-        },
-    ));
+    let clock = Arc::new(FixedClock(test_time()));
 
     //use the G722 payloader here, because it's very simple and all 0s is valid G722 data.
     let mut pktizer = PacketizerImpl {
         mtu: 100,
         payload_type: 98,
         ssrc: 0x1234ABCD,
+        csrc: vec![],
         payloader: g722,
         sequencer,
         timestamp: 45678,
+        timestamp_base: None,
         clock_rate: 90000,
         abs_send_time: 0,
-        time_gen,
+        transport_cc: 0,
+        transport_cc_sequencer: None,
+        extension_providers: vec![],
+        srtp_overhead: 0,
+        sender_stats: SenderStats::new(90000),
+        clock,
     };
     pktizer.enable_abs_send_time(1);
 
     let payload = Bytes::from_static(&[0x11, 0x12, 0x13, 0x14]);
-    let packets = pktizer.packetize(&payload, 2000).await?;
+    let packets = pktizer.packetize(&payload, 2000)?;
 
     let expected = Packet {
         header: Header {
@@ -83,6 +89,7 @@ async fn test_packetizer_abs_send_time() -> Result<()> {
             }],
         },
         payload: Bytes::from_static(&[0x11, 0x12, 0x13, 0x14]),
+        ..Default::default()
     };
 
     if packets.len() != 1 {
@@ -94,19 +101,533 @@ async fn test_packetizer_abs_send_time() -> Result<()> {
     Ok(())
 }
 
-#[tokio::test]
-async fn test_packetizer_timestamp_rollover_does_not_panic() -> Result<()> {
+#[test]
+fn test_packetizer_timestamp_rollover_does_not_panic() -> Result<()> {
     let g722 = Box::new(g7xx::G722Payloader {});
     let seq = Box::new(new_random_sequencer());
 
     let payload = Bytes::from_static(&[0; 128]);
     let mut packetizer = new_packetizer(100, 98, 0x1234ABCD, g722, seq, 90000);
 
-    packetizer.packetize(&payload, 10).await?;
+    packetizer.packetize(&payload, 10)?;
 
-    packetizer.packetize(&payload, u32::MAX).await?;
+    packetizer.packetize(&payload, u32::MAX)?;
 
     packetizer.skip_samples(u32::MAX);
 
     Ok(())
 }
+
+#[test]
+fn test_packetizer_transport_cc() -> Result<()> {
+    let payload = Bytes::from_static(&[0; 128]);
+    let transport_sequencer: Box<dyn Sequencer + Send + Sync> =
+        Box::new(new_fixed_sequencer(65534));
+
+    //two streams sharing the transport-wide sequencer
+    let mut video = new_packetizer(
+        100,
+        96,
+        0x1234ABCD,
+        Box::new(g7xx::G722Payloader {}),
+        Box::new(new_random_sequencer()),
+        90000,
+    );
+    video.enable_transport_cc(3, transport_sequencer.clone());
+    let mut audio = new_packetizer(
+        100,
+        111,
+        0x5678ABCD,
+        Box::new(g7xx::G722Payloader {}),
+        Box::new(new_random_sequencer()),
+        48000,
+    );
+    audio.enable_transport_cc(3, transport_sequencer);
+
+    let mut packets = video.packetize(&payload, 3000)?;
+    packets.extend(audio.packetize(&payload, 960)?);
+    assert_eq!(packets.len(), 4);
+
+    let expected = [65534u16, 65535, 0, 1];
+    for (packet, expected) in packets.iter().zip(expected.iter()) {
+        let raw = packet.header.get_extension(3);
+        assert!(raw.is_some(), "transport-cc extension not set");
+        let ext = TransportCcExtension::unmarshal(&mut raw.unwrap())?;
+        assert_eq!(ext.transport_sequence, *expected);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+struct RecordingExtensionProvider {
+    infos: Arc<std::sync::Mutex<Vec<PacketInfo>>>,
+}
+
+impl ExtensionProvider for RecordingExtensionProvider {
+    fn set_extensions(&mut self, header: &mut Header, info: &PacketInfo) -> Result<()> {
+        self.infos.lock().unwrap().push(*info);
+        header.set_extension(5, Bytes::from(vec![info.index as u8]))?;
+        Ok(())
+    }
+
+    fn max_extension_sizes(&self) -> Vec<(u8, usize)> {
+        vec![(5, 1)]
+    }
+
+    fn clone_to(&self) -> Box<dyn ExtensionProvider + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn test_packetizer_extension_providers() -> Result<()> {
+    let payload = Bytes::from_static(&[0; 128]);
+    let mut packetizer = new_packetizer(
+        100,
+        98,
+        0x1234ABCD,
+        Box::new(g7xx::G722Payloader {}),
+        Box::new(new_random_sequencer()),
+        90000,
+    );
+
+    let recorder = RecordingExtensionProvider::default();
+    packetizer.add_extension_provider(Box::new(FixedExtensionProvider {
+        id: 1,
+        payload: Bytes::from_static(b"0"),
+        first_packet_only: true,
+    }));
+    packetizer.add_extension_provider(Box::new(recorder.clone()));
+
+    let packets = packetizer.packetize(&payload, 2000)?;
+    assert_eq!(packets.len(), 2);
+
+    assert_eq!(
+        packets[0].header.get_extension(1),
+        Some(Bytes::from_static(b"0"))
+    );
+    assert_eq!(packets[1].header.get_extension(1), None);
+    for (i, packet) in packets.iter().enumerate() {
+        assert_eq!(
+            packet.header.get_extension(5),
+            Some(Bytes::from(vec![i as u8]))
+        );
+    }
+
+    assert_eq!(
+        *recorder.infos.lock().unwrap(),
+        vec![
+            PacketInfo {
+                index: 0,
+                is_first: true,
+                is_last: false,
+                samples: 2000,
+            },
+            PacketInfo {
+                index: 1,
+                is_first: false,
+                is_last: true,
+                samples: 2000,
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_mtu_includes_overhead() -> Result<()> {
+    const MTU: usize = 100;
+    const SRTP_OVERHEAD: usize = 10;
+
+    let payload = Bytes::from_static(&[0; 1000]);
+    let mut packetizer = new_packetizer(
+        MTU,
+        98,
+        0x1234ABCD,
+        Box::new(g7xx::G722Payloader {}),
+        Box::new(new_random_sequencer()),
+        90000,
+    );
+    packetizer.enable_abs_send_time(1);
+    packetizer.enable_transport_cc(2, Box::new(new_random_sequencer()));
+    packetizer.add_extension_provider(Box::new(FixedExtensionProvider {
+        id: 3,
+        payload: Bytes::from_static(b"video"),
+        first_packet_only: true,
+    }));
+    packetizer.set_csrc(vec![1, 2]);
+    packetizer.set_srtp_overhead(SRTP_OVERHEAD);
+
+    let packets = packetizer.packetize(&payload, 3000)?;
+    // 12 bytes header, 8 bytes CSRC, 4 + 16 bytes extensions, 10 bytes SRTP
+    assert_eq!(packets[0].payload.len(), MTU - 50);
+    for packet in &packets {
+        assert_eq!(packet.header.csrc, vec![1, 2]);
+        assert!(packet.marshal_size() + SRTP_OVERHEAD <= MTU);
+    }
+
+    packetizer.set_srtp_overhead(MTU);
+    let result = packetizer.packetize(&payload, 3000);
+    assert_eq!(Err(Error::ErrMtuTooSmall(MTU, MTU + 40)), result);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct EmptyExtensionProvider;
+
+impl ExtensionProvider for EmptyExtensionProvider {
+    fn set_extensions(&mut self, _header: &mut Header, _info: &PacketInfo) -> Result<()> {
+        Ok(())
+    }
+
+    fn max_extension_sizes(&self) -> Vec<(u8, usize)> {
+        vec![]
+    }
+
+    fn clone_to(&self) -> Box<dyn ExtensionProvider + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn test_packetizer_mtu_two_byte_extension_id() -> Result<()> {
+    const MTU: usize = 100;
+
+    let payload = Bytes::from_static(&[0; 1000]);
+    let mut packetizer = new_packetizer(
+        MTU,
+        98,
+        0x1234ABCD,
+        Box::new(g7xx::G722Payloader {}),
+        Box::new(new_random_sequencer()),
+        90000,
+    );
+    // providers setting no extension reserve nothing
+    packetizer.add_extension_provider(Box::new(EmptyExtensionProvider));
+    packetizer.add_extension_provider(Box::new(FixedExtensionProvider {
+        id: 3,
+        payload: Bytes::from_static(b"abc"),
+        first_packet_only: true,
+    }));
+    // an id above 14 needs the two-byte profile
+    packetizer.add_extension_provider(Box::new(FixedExtensionProvider {
+        id: 15,
+        payload: Bytes::from_static(b"abc"),
+        first_packet_only: true,
+    }));
+
+    let packets = packetizer.packetize(&payload, 3000)?;
+    // 12 bytes header, 4 + 10 bytes two-byte extensions padded to 12
+    assert_eq!(packets[0].payload.len(), MTU - 28);
+    assert_eq!(packets[0].marshal_size(), MTU);
+    assert_eq!(
+        packets[0].header.extension_profile,
+        EXTENSION_PROFILE_TWO_BYTE
+    );
+
+    let raw = packets[0].marshal()?;
+    let packet = Packet::unmarshal(&mut raw.clone())?;
+    assert_eq!(
+        packet.header.get_extension(15),
+        Some(Bytes::from_static(b"abc"))
+    );
+
+    // packets without extensions have no extension block
+    assert!(!packets[1].header.extension);
+    assert_eq!(packets[1].header.marshal_size(), 12);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_packetizer_time_gen() -> Result<()> {
+    let time_gen: Option<FnTimeGen> = Some(Arc::new(
+        || -> Pin<Box<dyn Future<Output = SystemTime> + Send + 'static>> {
+            Box::pin(async move { test_time() })
+        },
+    ));
+
+    let packetizer = new_packetizer(
+        100,
+        98,
+        0x1234ABCD,
+        Box::new(g7xx::G722Payloader {}),
+        Box::new(new_fixed_sequencer(1234)),
+        90000,
+    );
+    let mut packetizer = AsyncPacketizer::new(Box::new(packetizer), time_gen);
+    packetizer.packetizer().enable_abs_send_time(1);
+
+    let payload = Bytes::from_static(&[0x11, 0x12, 0x13, 0x14]);
+    let packets = packetizer.packetize(&payload, 2000).await?;
+    assert_eq!(packets.len(), 1);
+    assert_eq!(
+        packets[0].header.get_extension(1),
+        Some(Bytes::from_static(&[0x40, 0, 0]))
+    );
+
+    // the clock of the packetizer is not replaced by the generated time
+    let packet = packetizer.packetizer().generate_padding(10)?;
+    assert_ne!(
+        packet.header.get_extension(1),
+        Some(Bytes::from_static(&[0x40, 0, 0]))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_packetizer_time_gen_unused() -> Result<()> {
+    let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = calls.clone();
+    let time_gen: Option<FnTimeGen> = Some(Arc::new(
+        move || -> Pin<Box<dyn Future<Output = SystemTime> + Send + 'static>> {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Box::pin(async move { test_time() })
+        },
+    ));
+
+    let packetizer = new_packetizer(
+        100,
+        98,
+        0x1234ABCD,
+        Box::new(g7xx::G722Payloader {}),
+        Box::new(new_fixed_sequencer(1234)),
+        90000,
+    );
+    let mut packetizer = AsyncPacketizer::new(Box::new(packetizer), time_gen);
+
+    // abs-send-time is disabled
+    let payload = Bytes::from_static(&[0x11, 0x12, 0x13, 0x14]);
+    assert_eq!(packetizer.packetize(&payload, 2000).await?.len(), 1);
+
+    // no packets come out
+    packetizer.packetizer().enable_abs_send_time(1);
+    assert!(packetizer.packetize(&Bytes::new(), 2000).await?.is_empty());
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct ManualClock(Arc<std::sync::Mutex<SystemTime>>);
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+}
+
+#[test]
+fn test_packetizer_presentation_time() -> Result<()> {
+    let payload = Bytes::from_static(&[0; 10]);
+    let now = Arc::new(std::sync::Mutex::new(test_time()));
+    let mut packetizer = PacketizerImpl {
+        mtu: 100,
+        payload_type: 98,
+        ssrc: 0x1234ABCD,
+        csrc: vec![],
+        payloader: Box::new(g7xx::G722Payloader {}),
+        sequencer: Box::new(new_random_sequencer()),
+        timestamp: u32::MAX - 1000,
+        timestamp_base: None,
+        clock_rate: 90000,
+        abs_send_time: 0,
+        transport_cc: 0,
+        transport_cc_sequencer: None,
+        extension_providers: vec![],
+        srtp_overhead: 0,
+        sender_stats: SenderStats::new(90000),
+        clock: Arc::new(ManualClock(now.clone())),
+    };
+    assert_eq!(packetizer.timestamp_mapping(), None);
+
+    // variable frame intervals, wrapping around after the second frame
+    let expected = [
+        (Duration::from_millis(5000), u32::MAX - 1000),
+        (Duration::from_millis(5010), u32::MAX - 100),
+        (Duration::from_millis(5050), 3499),
+        (Duration::from_millis(5040), 2599),
+    ];
+    for (presentation_time, timestamp) in expected {
+        let packets = packetizer.packetize_at(&payload, presentation_time)?;
+        assert_eq!(packets[0].header.timestamp, timestamp);
+    }
+
+    // the mapping is extrapolated from the last packet sent, like sender reports
+    *now.lock().unwrap() += Duration::from_secs(1);
+    let mapping = packetizer.timestamp_mapping().unwrap();
+    assert_eq!(
+        mapping.ntp_time,
+        unix2ntp(test_time() + Duration::from_secs(1))
+    );
+    assert_eq!(mapping.rtp_time, 92599);
+    assert_eq!(
+        Some(mapping.rtp_time),
+        packetizer
+            .sender_stats()
+            .sender_info(test_time() + Duration::from_secs(1))
+            .map(|info| info.rtp_time)
+    );
+
+    *now.lock().unwrap() = test_time() - Duration::from_millis(10);
+    let mapping = packetizer.timestamp_mapping().unwrap();
+    assert_eq!(mapping.rtp_time, 1699);
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_builder() -> Result<()> {
+    let payload = Bytes::from_static(&[0; 128]);
+    let mut packetizer = PacketizerBuilder::new(98, 8000, Box::new(g7xx::G722Payloader {}))
+        .with_mtu(100)
+        .with_ssrc(0x1234ABCD)
+        .with_csrc(vec![1])
+        .with_sequencer(Box::new(new_fixed_sequencer(100)))
+        .with_initial_timestamp(1000)
+        .with_clock(Arc::new(FixedClock(test_time())))
+        .with_abs_send_time(1)
+        .build();
+    assert_eq!(packetizer.ssrc(), 0x1234ABCD);
+    assert_eq!(packetizer.payload_type(), 98);
+    assert_eq!(packetizer.timestamp(), 1000);
+
+    let packets = packetizer.packetize(&payload, 160)?;
+    assert_eq!(packets.len(), 2);
+    for (i, packet) in packets.iter().enumerate() {
+        assert_eq!(packet.header.payload_type, 98);
+        assert_eq!(packet.header.ssrc, 0x1234ABCD);
+        assert_eq!(packet.header.csrc, vec![1]);
+        assert_eq!(packet.header.sequence_number, 100 + i as u16);
+        assert_eq!(packet.header.timestamp, 1000);
+    }
+    assert_eq!(
+        packets[1].header.get_extension(1),
+        Some(Bytes::from_static(&[0x40, 0, 0]))
+    );
+    assert_eq!(packetizer.timestamp(), 1160);
+
+    packetizer.set_ssrc(0x5678ABCD);
+    packetizer.set_payload_type(111);
+    packetizer.set_timestamp(5000);
+    let packets = packetizer.packetize(&payload, 160)?;
+    assert_eq!(packets[0].header.ssrc, 0x5678ABCD);
+    assert_eq!(packets[0].header.payload_type, 111);
+    assert_eq!(packets[0].header.timestamp, 5000);
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_generate_padding() -> Result<()> {
+    let payload = Bytes::from_static(&[0; 10]);
+    let mut packetizer = PacketizerBuilder::new(98, 90000, Box::new(g7xx::G722Payloader {}))
+        .with_ssrc(0x1234ABCD)
+        .with_sequencer(Box::new(new_fixed_sequencer(100)))
+        .with_initial_timestamp(1000)
+        .with_transport_cc(2, Box::new(new_fixed_sequencer(7)))
+        .build();
+    packetizer.packetize(&payload, 3000)?;
+
+    let packet = packetizer.generate_padding(200)?;
+    assert!(packet.header.padding);
+    assert!(!packet.header.marker);
+    assert_eq!(packet.header.ssrc, 0x1234ABCD);
+    assert_eq!(packet.header.sequence_number, 101);
+    assert_eq!(packet.header.timestamp, 4000);
+    assert!(packet.payload.is_empty());
+    let raw = packet.header.get_extension(2).unwrap();
+    assert_eq!(
+        TransportCcExtension::unmarshal(&mut raw.clone())?.transport_sequence,
+        8
+    );
+
+    let raw = packet.marshal()?;
+    assert_eq!(raw.len(), packet.header.marshal_size() + 200);
+    assert_eq!(raw[raw.len() - 1], 200);
+
+    assert_eq!(
+        Err(Error::ErrPaddingSizeInvalid(0)),
+        packetizer.generate_padding(0)
+    );
+    assert_eq!(
+        Err(Error::ErrPaddingSizeInvalid(256)),
+        packetizer.generate_padding(256)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_generate_padding_mtu() -> Result<()> {
+    const MTU: usize = 100;
+
+    let mut packetizer = PacketizerBuilder::new(98, 90000, Box::new(g7xx::G722Payloader {}))
+        .with_mtu(MTU)
+        .with_sequencer(Box::new(new_fixed_sequencer(100)))
+        .with_transport_cc(2, Box::new(new_fixed_sequencer(7)))
+        .with_extension_provider(Box::new(FixedExtensionProvider {
+            id: 3,
+            payload: Bytes::from_static(b"abc"),
+            first_packet_only: true,
+        }))
+        .with_srtp_overhead(10)
+        .build();
+
+    // 12 bytes header, 4 + 7 bytes extensions padded to 12 and 10 bytes SRTP
+    // overhead leave 66 bytes of padding
+    assert_eq!(
+        Err(Error::ErrMtuTooSmall(MTU, MTU + 1)),
+        packetizer.generate_padding(67)
+    );
+
+    let packet = packetizer.generate_padding(66)?;
+    assert_eq!(packet.header.sequence_number, 100);
+    assert_eq!(
+        packet.header.get_extension(3),
+        Some(Bytes::from_static(b"abc"))
+    );
+    assert!(packet.header.get_extension(2).is_some());
+    assert_eq!(packet.marshal_size() + 10, MTU);
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_sender_stats() -> Result<()> {
+    let payload = Bytes::from_static(&[0; 128]);
+    let mut packetizer = PacketizerBuilder::new(98, 90000, Box::new(g7xx::G722Payloader {}))
+        .with_mtu(100)
+        .with_initial_timestamp(1000)
+        .with_clock(Arc::new(FixedClock(test_time())))
+        .build();
+    assert_eq!(packetizer.sender_stats().last_timestamp(), None);
+
+    packetizer.packetize(&payload, 3000)?;
+    packetizer.generate_padding(50)?;
+
+    // the padding carries the next timestamp and does not move the anchor
+    let stats = packetizer.sender_stats();
+    assert_eq!(stats.packet_count(), 3);
+    assert_eq!(stats.octet_count(), 128);
+    assert_eq!(stats.last_timestamp(), Some((1000, test_time())));
+
+    let info = stats
+        .sender_info(test_time() + Duration::from_millis(100))
+        .unwrap();
+    assert_eq!(info.rtp_time, 10000);
+    assert_eq!(info.packet_count, 3);
+    assert_eq!(info.octet_count, 128);
+
+    // a new SSRC starts over
+    packetizer.set_ssrc(0x5678ABCD);
+    let stats = packetizer.sender_stats();
+    assert_eq!(stats.packet_count(), 0);
+    assert_eq!(stats.last_timestamp(), None);
+
+    Ok(())
+}
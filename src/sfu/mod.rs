@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod sfu_test;
+
+pub mod simulcast_selector;
+pub mod stream_rewriter;
+pub mod svc_filter;
+
+use crate::codecs::{h264::*, vp8::Vp8Packet, vp9::Vp9Packet};
+use crate::error::Result;
+use crate::packetizer::Depacketizer;
+
+use bytes::Bytes;
+
+/// VideoCodec is the codec of a forwarded video track
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VideoCodec {
+    Vp8,
+    Vp9,
+    H264,
+}
+
+/// FrameInfo describes the frame a packet belongs to, as far as forwarding is concerned
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// the packet starts a keyframe, decodable without any previous frame
+    pub keyframe: bool,
+    /// the packet is the first of a frame, or for H264 of a NAL unit
+    pub start_of_frame: bool,
+    /// temporal layer of the frame, 0 when the codec has no temporal layers
+    pub temporal_id: u8,
+    /// the frame only depends on lower temporal layers, so forwarding can switch
+    /// up to its temporal layer, the VP8 y or VP9 u flag
+    pub switching_point: bool,
+}
+
+impl FrameInfo {
+    /// parse reads the frame info of an RTP payload. An empty payload, e.g. a
+    /// padding-only packet, has the default frame info.
+    pub fn parse(codec: VideoCodec, payload: &Bytes) -> Result<Self> {
+        if payload.is_empty() {
+            return Ok(FrameInfo::default());
+        }
+
+        match codec {
+            VideoCodec::Vp8 => {
+                let mut vp8 = Vp8Packet::default();
+                let frame = vp8.depacketize(payload)?;
+                let start_of_frame = vp8.s == 1 && vp8.pid == 0;
+                Ok(FrameInfo {
+                    // the inverse key frame flag is the first bit of the frame tag
+                    keyframe: start_of_frame && frame[0] & 0x01 == 0,
+                    start_of_frame,
+                    temporal_id: if vp8.t == 1 { vp8.tid } else { 0 },
+                    switching_point: vp8.t == 1 && vp8.y == 1,
+                })
+            }
+            VideoCodec::Vp9 => {
+                let mut vp9 = Vp9Packet::default();
+                vp9.depacketize(payload)?;
+                Ok(FrameInfo {
+                    keyframe: !vp9.p && vp9.b && vp9.sid == 0,
+                    start_of_frame: vp9.b,
+                    temporal_id: vp9.tid,
+                    switching_point: vp9.u,
+                })
+            }
+            VideoCodec::H264 => {
+                let keyframe = h264_has_keyframe_nalu(payload);
+                Ok(FrameInfo {
+                    keyframe,
+                    start_of_frame: h264_starts_nalu(payload),
+                    temporal_id: 0,
+                    switching_point: false,
+                })
+            }
+        }
+    }
+}
+
+/// h264_starts_nalu reports whether an H264 payload starts a NAL unit, i.e. it
+/// is not a FU-A fragment other than the first one
+fn h264_starts_nalu(payload: &Bytes) -> bool {
+    match payload[0] & NALU_TYPE_BITMASK {
+        FUA_NALU_TYPE => payload.len() >= FUA_HEADER_SIZE && payload[1] & FU_START_BITMASK != 0,
+        _ => true,
+    }
+}
+
+/// h264_has_keyframe_nalu reports whether an H264 payload starts an IDR picture
+/// or carries an SPS, looking into STAP-A and the first FU-A fragment
+fn h264_has_keyframe_nalu(payload: &Bytes) -> bool {
+    let is_keyframe_type = |nalu_type: u8| nalu_type == IDR_NALU_TYPE || nalu_type == SPS_NALU_TYPE;
+
+    match payload[0] & NALU_TYPE_BITMASK {
+        STAPA_NALU_TYPE => {
+            let mut offset = STAPA_HEADER_SIZE;
+            while offset + STAPA_NALU_LENGTH_SIZE < payload.len() {
+                let nalu_size = ((payload[offset] as usize) << 8) | payload[offset + 1] as usize;
+                offset += STAPA_NALU_LENGTH_SIZE;
+                if nalu_size == 0 || offset + nalu_size > payload.len() {
+                    return false;
+                }
+                if is_keyframe_type(payload[offset] & NALU_TYPE_BITMASK) {
+                    return true;
+                }
+                offset += nalu_size;
+            }
+            false
+        }
+        FUA_NALU_TYPE => {
+            payload.len() >= FUA_HEADER_SIZE
+                && payload[1] & FU_START_BITMASK != 0
+                && is_keyframe_type(payload[1] & NALU_TYPE_BITMASK)
+        }
+        nalu_type => is_keyframe_type(nalu_type),
+    }
+}
@@ -0,0 +1,98 @@
+use super::*;
+
+#[test]
+fn test_frame_info_vp8() -> Result<()> {
+    // S=1, PID=0, T with tid 1 and y, then a key frame tag
+    let keyframe = Bytes::from_static(&[0x90, 0x20, 0x60, 0x10, 0x02]);
+    assert_eq!(
+        FrameInfo::parse(VideoCodec::Vp8, &keyframe)?,
+        FrameInfo {
+            keyframe: true,
+            start_of_frame: true,
+            temporal_id: 1,
+            switching_point: true,
+        }
+    );
+
+    // inter frame tag
+    let delta_frame = Bytes::from_static(&[0x90, 0x20, 0x80, 0x11, 0x02]);
+    assert_eq!(
+        FrameInfo::parse(VideoCodec::Vp8, &delta_frame)?,
+        FrameInfo {
+            keyframe: false,
+            start_of_frame: true,
+            temporal_id: 2,
+            switching_point: false,
+        }
+    );
+
+    // continuation of a key frame
+    let continuation = Bytes::from_static(&[0x80, 0x20, 0x60, 0x10, 0x02]);
+    let info = FrameInfo::parse(VideoCodec::Vp8, &continuation)?;
+    assert!(!info.keyframe);
+    assert!(!info.start_of_frame);
+
+    assert_eq!(
+        FrameInfo::parse(VideoCodec::Vp8, &Bytes::new())?,
+        FrameInfo::default()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_info_vp9() -> Result<()> {
+    // L=1, B=1, tid 0, sid 0, non flexible tl0picidx
+    let keyframe = Bytes::from_static(&[0x28, 0x00, 0x01, 0xAA]);
+    let info = FrameInfo::parse(VideoCodec::Vp9, &keyframe)?;
+    assert!(info.keyframe);
+    assert!(info.start_of_frame);
+
+    // P=1, L=1, B=1, tid 2 with u, sid 0
+    let delta_frame = Bytes::from_static(&[0x68, 0x50, 0x01, 0xAA]);
+    assert_eq!(
+        FrameInfo::parse(VideoCodec::Vp9, &delta_frame)?,
+        FrameInfo {
+            keyframe: false,
+            start_of_frame: true,
+            temporal_id: 2,
+            switching_point: true,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_info_h264() -> Result<()> {
+    let tests: Vec<(&str, &[u8], bool, bool)> = vec![
+        ("Idr", &[0x65, 0x88], true, true),
+        ("Sps", &[0x67, 0x42], true, true),
+        ("NonIdr", &[0x41, 0x9a], false, true),
+        (
+            "StapASpsPps",
+            &[0x78, 0x00, 0x02, 0x67, 0x42, 0x00, 0x02, 0x68, 0xce],
+            true,
+            true,
+        ),
+        ("StapAPps", &[0x78, 0x00, 0x02, 0x68, 0xce], false, true),
+        (
+            "StapATruncated",
+            &[0x78, 0x00, 0x05, 0x67, 0x42],
+            false,
+            true,
+        ),
+        ("FuAIdrStart", &[0x7c, 0x85, 0x88], true, true),
+        ("FuAIdrMiddle", &[0x7c, 0x05, 0x88], false, false),
+        ("FuANonIdrStart", &[0x7c, 0x81, 0x9a], false, true),
+        ("FuANonIdrEnd", &[0x7c, 0x41, 0x9a], false, false),
+    ];
+
+    for (name, payload, keyframe, start_of_frame) in tests {
+        let info = FrameInfo::parse(VideoCodec::H264, &Bytes::copy_from_slice(payload))?;
+        assert_eq!(info.keyframe, keyframe, "{}", name);
+        assert_eq!(info.start_of_frame, start_of_frame, "{}", name);
+    }
+
+    Ok(())
+}
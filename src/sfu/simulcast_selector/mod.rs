@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod simulcast_selector_test;
+
+use super::{FrameInfo, VideoCodec};
+use crate::error::Result;
+use crate::packet::Packet;
+use crate::packetizer::{Clock, SystemClock};
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// default minimum time between two keyframe requests for the same switch
+const KEYFRAME_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// KeyframeRequester is called with the RID of an encoding when switching to it
+/// waits for a keyframe, e.g. to send a PLI
+pub type KeyframeRequester = Box<dyn FnMut(&str) + Send>;
+
+/// Selection tells what to do with a packet given to a SimulcastSelector
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Selection {
+    /// forward the packet, switched is set on the first packet of a newly
+    /// selected encoding
+    Forward { switched: bool },
+    /// do not forward the packet of the forwarded encoding, e.g. a filtered
+    /// temporal layer, and skip its sequence number, see StreamRewriter::drop_packet
+    Skip,
+    /// do not forward the packet of another encoding
+    Drop,
+}
+
+/// SimulcastSelector forwards one of the RID identified encodings of a track.
+/// It switches encodings only at a keyframe of the target encoding and
+/// switches up temporal layers only at switching points. While a switch is
+/// pending, the keyframe request is repeated on packets of the target encoding,
+/// at most once per keyframe request interval, in case a request was lost.
+pub struct SimulcastSelector {
+    codec: VideoCodec,
+    current: Option<String>,
+    target: Option<String>,
+    current_temporal_id: u8,
+    target_temporal_id: u8,
+    on_keyframe_request: Option<KeyframeRequester>,
+    keyframe_request_interval: Duration,
+    last_keyframe_request: Option<SystemTime>,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl fmt::Debug for SimulcastSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SimulcastSelector")
+            .field("codec", &self.codec)
+            .field("current", &self.current)
+            .field("target", &self.target)
+            .field("current_temporal_id", &self.current_temporal_id)
+            .field("target_temporal_id", &self.target_temporal_id)
+            .field("keyframe_request_interval", &self.keyframe_request_interval)
+            .field("last_keyframe_request", &self.last_keyframe_request)
+            .finish()
+    }
+}
+
+impl SimulcastSelector {
+    /// new creates a selector forwarding all temporal layers of no encoding
+    /// until a target is set
+    pub fn new(codec: VideoCodec) -> Self {
+        SimulcastSelector {
+            codec,
+            current: None,
+            target: None,
+            current_temporal_id: u8::MAX,
+            target_temporal_id: u8::MAX,
+            on_keyframe_request: None,
+            keyframe_request_interval: KEYFRAME_REQUEST_INTERVAL,
+            last_keyframe_request: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// on_keyframe_request sets the callback requesting a keyframe of the target
+    /// encoding
+    pub fn on_keyframe_request(&mut self, requester: KeyframeRequester) {
+        self.on_keyframe_request = Some(requester);
+    }
+
+    /// set_keyframe_request_interval sets the minimum time between two keyframe
+    /// requests while a switch is pending
+    pub fn set_keyframe_request_interval(&mut self, interval: Duration) {
+        self.keyframe_request_interval = interval;
+    }
+
+    /// set_clock sets the clock used to rate limit keyframe requests
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock + Send + Sync>) {
+        self.clock = clock;
+    }
+
+    /// current returns the RID of the forwarded encoding
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// target returns the RID of the encoding to forward
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// is_switch_pending reports whether the selector waits for a keyframe of the
+    /// target encoding
+    pub fn is_switch_pending(&self) -> bool {
+        self.target != self.current
+    }
+
+    /// set_target selects the encoding to forward, requesting a keyframe of it if
+    /// it is not forwarded yet
+    pub fn set_target(&mut self, rid: &str) {
+        if self.target.as_deref() != Some(rid) {
+            self.last_keyframe_request = None;
+        }
+        self.target = Some(rid.to_owned());
+        if self.is_switch_pending() {
+            self.request_keyframe();
+        }
+    }
+
+    /// request_keyframe calls the keyframe requester for the target encoding,
+    /// unless it was called within the keyframe request interval
+    fn request_keyframe(&mut self) {
+        let now = self.clock.now();
+        if let Some(last_keyframe_request) = self.last_keyframe_request {
+            let elapsed = now
+                .duration_since(last_keyframe_request)
+                .unwrap_or_default();
+            if elapsed < self.keyframe_request_interval {
+                return;
+            }
+        }
+
+        if let (Some(requester), Some(target)) = (&mut self.on_keyframe_request, &self.target) {
+            requester(target);
+            self.last_keyframe_request = Some(now);
+        }
+    }
+
+    /// set_target_temporal_layer selects the highest temporal layer to forward
+    pub fn set_target_temporal_layer(&mut self, temporal_id: u8) {
+        self.target_temporal_id = temporal_id;
+    }
+
+    /// select decides whether to forward a packet of the encoding rid
+    pub fn select(&mut self, rid: &str, packet: &Packet) -> Result<Selection> {
+        let is_target = self.target.as_deref() == Some(rid);
+        let is_current = self.current.as_deref() == Some(rid);
+        if !is_target && !is_current {
+            return Ok(Selection::Drop);
+        }
+
+        let info = FrameInfo::parse(self.codec, &packet.payload)?;
+        if is_target && !is_current {
+            if info.keyframe {
+                self.current = self.target.clone();
+                self.current_temporal_id = self.target_temporal_id;
+                self.last_keyframe_request = None;
+                return Ok(Selection::Forward { switched: true });
+            }
+            self.request_keyframe();
+        }
+
+        Ok(self.select_current(rid, &info))
+    }
+
+    /// select_current applies temporal layer selection to a packet, if it
+    /// belongs to the forwarded encoding
+    fn select_current(&mut self, rid: &str, info: &FrameInfo) -> Selection {
+        if self.current.as_deref() != Some(rid) {
+            return Selection::Drop;
+        }
+
+        if info.start_of_frame {
+            if self.target_temporal_id < self.current_temporal_id {
+                self.current_temporal_id = self.target_temporal_id;
+            } else if info.temporal_id > self.current_temporal_id
+                && info.temporal_id <= self.target_temporal_id
+                && (info.switching_point || info.keyframe)
+            {
+                self.current_temporal_id = info.temporal_id;
+            }
+        }
+
+        if info.temporal_id <= self.current_temporal_id {
+            Selection::Forward { switched: false }
+        } else {
+            Selection::Skip
+        }
+    }
+}
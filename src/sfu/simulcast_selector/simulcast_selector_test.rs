@@ -0,0 +1,210 @@
+use super::*;
+use crate::header::Header;
+
+use bytes::Bytes;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// VP8 packet of a frame with the given temporal layer and y flag
+fn vp8_packet(start_of_frame: bool, keyframe: bool, tid: u8, y: bool) -> Packet {
+    Packet {
+        header: Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from(vec![
+            if start_of_frame { 0x90 } else { 0x80 },
+            0x20,
+            tid << 6 | (y as u8) << 5,
+            if keyframe { 0x10 } else { 0x11 },
+            0x02,
+        ]),
+        ..Default::default()
+    }
+}
+
+fn requests(selector: &mut SimulcastSelector) -> Arc<Mutex<Vec<String>>> {
+    let requests = Arc::new(Mutex::new(vec![]));
+    let recorder = requests.clone();
+    selector.on_keyframe_request(Box::new(move |rid: &str| {
+        recorder.lock().unwrap().push(rid.to_owned())
+    }));
+    requests
+}
+
+#[test]
+fn test_simulcast_selector_switch_at_keyframe() -> Result<()> {
+    let mut selector = SimulcastSelector::new(VideoCodec::Vp8);
+    let requests = requests(&mut selector);
+
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, true, 0, false))?,
+        Selection::Drop
+    );
+
+    selector.set_target("h");
+    assert!(selector.is_switch_pending());
+    assert_eq!(*requests.lock().unwrap(), vec!["h"]);
+
+    // waits for a keyframe of the target
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 0, false))?,
+        Selection::Drop
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, true, 0, false))?,
+        Selection::Forward { switched: true }
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(false, false, 0, false))?,
+        Selection::Forward { switched: false }
+    );
+    assert_eq!(selector.current(), Some("h"));
+    assert!(!selector.is_switch_pending());
+
+    // keeps forwarding the current encoding until the target sends a keyframe
+    selector.set_target("l");
+    assert_eq!(*requests.lock().unwrap(), vec!["h", "l"]);
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 0, false))?,
+        Selection::Forward { switched: false }
+    );
+    assert_eq!(
+        selector.select("l", &vp8_packet(true, false, 0, false))?,
+        Selection::Drop
+    );
+    assert_eq!(
+        selector.select("m", &vp8_packet(true, true, 0, false))?,
+        Selection::Drop
+    );
+    assert_eq!(
+        selector.select("l", &vp8_packet(true, true, 0, false))?,
+        Selection::Forward { switched: true }
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 0, false))?,
+        Selection::Drop
+    );
+    assert_eq!(selector.current(), Some("l"));
+
+    // selecting the current encoding does not request a keyframe
+    selector.set_target("l");
+    assert_eq!(requests.lock().unwrap().len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_simulcast_selector_temporal_layers() -> Result<()> {
+    let mut selector = SimulcastSelector::new(VideoCodec::Vp8);
+    selector.set_target("h");
+    selector.set_target_temporal_layer(0);
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, true, 0, false))?,
+        Selection::Forward { switched: true }
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 1, true))?,
+        Selection::Skip
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(false, false, 1, true))?,
+        Selection::Skip
+    );
+
+    // switching up waits for a switching point
+    selector.set_target_temporal_layer(2);
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 2, false))?,
+        Selection::Skip
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 1, true))?,
+        Selection::Forward { switched: false }
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(false, false, 1, true))?,
+        Selection::Forward { switched: false }
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 2, false))?,
+        Selection::Skip
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 2, true))?,
+        Selection::Forward { switched: false }
+    );
+
+    // switching down happens at the next frame
+    selector.set_target_temporal_layer(0);
+    assert_eq!(
+        selector.select("h", &vp8_packet(false, false, 2, true))?,
+        Selection::Forward { switched: false }
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 1, true))?,
+        Selection::Skip
+    );
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, false, 0, false))?,
+        Selection::Forward { switched: false }
+    );
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct ManualClock(Arc<Mutex<SystemTime>>);
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+}
+
+#[test]
+fn test_simulcast_selector_repeat_keyframe_request() -> Result<()> {
+    let now = Arc::new(Mutex::new(SystemTime::UNIX_EPOCH));
+    let advance = |millis: u64| *now.lock().unwrap() += Duration::from_millis(millis);
+
+    let mut selector = SimulcastSelector::new(VideoCodec::Vp8);
+    selector.set_clock(Arc::new(ManualClock(now.clone())));
+    selector.set_keyframe_request_interval(Duration::from_millis(500));
+    let requests = requests(&mut selector);
+
+    selector.set_target("h");
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    // the request is repeated on packets of the target, at most once per interval
+    let delta = vp8_packet(true, false, 0, false);
+    advance(100);
+    assert_eq!(selector.select("h", &delta)?, Selection::Drop);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    advance(400);
+    assert_eq!(selector.select("h", &delta)?, Selection::Drop);
+    assert_eq!(selector.select("h", &delta)?, Selection::Drop);
+    assert_eq!(*requests.lock().unwrap(), vec!["h", "h"]);
+
+    // packets of other encodings do not request
+    advance(500);
+    assert_eq!(selector.select("l", &delta)?, Selection::Drop);
+    assert_eq!(requests.lock().unwrap().len(), 2);
+
+    // no request once switched
+    assert_eq!(
+        selector.select("h", &vp8_packet(true, true, 0, false))?,
+        Selection::Forward { switched: true }
+    );
+    advance(500);
+    assert_eq!(
+        selector.select("h", &delta)?,
+        Selection::Forward { switched: false }
+    );
+    assert_eq!(requests.lock().unwrap().len(), 2);
+
+    // a new target requests right away
+    selector.set_target("l");
+    assert_eq!(*requests.lock().unwrap(), vec!["h", "h", "l"]);
+
+    Ok(())
+}
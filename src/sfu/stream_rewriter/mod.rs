@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod stream_rewriter_test;
+
+use crate::codecs::vp8::Vp8Packet;
+use crate::error::Result;
+use crate::packet::Packet;
+use crate::packetizer::Depacketizer;
+
+use bytes::{Bytes, BytesMut};
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+const VP8_PICTURE_ID_MASK: u16 = 0x7FFF;
+/// packets later than this many sequence numbers behind the highest one are not
+/// forwarded, bounding the dropped sequence numbers to keep
+const MAX_REORDER: u16 = 512;
+
+/// is_newer reports whether sequence number a comes after b, accounting for wrap
+fn is_newer(a: u16, b: u16) -> bool {
+    a != b && a.wrapping_sub(b) < 0x8000
+}
+
+/// SourceState holds the mapping of the current source onto the outgoing stream
+#[derive(Debug, Default, Clone)]
+struct SourceState {
+    ssrc: u32,
+    first_sequence_number: u16,
+    highest_sequence_number: u16,
+    /// sequence number offset of packets after the last dropped one
+    sequence_number_offset: u16,
+    /// dropped sequence numbers within MAX_REORDER of the highest one
+    dropped: VecDeque<u16>,
+    timestamp_offset: u32,
+    picture_id_offset: u16,
+    tl0_pic_idx_offset: u8,
+}
+
+impl SourceState {
+    /// offset returns the sequence number offset applying at the position of a
+    /// packet, each drop after it lowering the latest offset by one
+    fn offset(&self, sequence_number: u16) -> u16 {
+        let later_drops = self
+            .dropped
+            .iter()
+            .filter(|&&dropped| is_newer(dropped, sequence_number))
+            .count();
+        self.sequence_number_offset.wrapping_add(later_drops as u16)
+    }
+
+    /// is_too_late reports whether a packet is older than the reorder window
+    fn is_too_late(&self, sequence_number: u16) -> bool {
+        is_newer(
+            self.highest_sequence_number.wrapping_sub(MAX_REORDER),
+            sequence_number,
+        )
+    }
+}
+
+/// StreamRewriter maps packets of varying sources, e.g. simulcast layers or
+/// publishers, onto one outgoing SSRC with continuous sequence numbers and
+/// timestamps. Packets of a new SSRC switch the source.
+#[derive(Debug, Clone)]
+pub struct StreamRewriter {
+    ssrc: u32,
+    clock_rate: u32,
+    rewrite_vp8: bool,
+
+    source: Option<SourceState>,
+    last_sequence_number: u16,
+    last_timestamp: u32,
+    last_arrival: Option<SystemTime>,
+    last_picture_id: u16,
+    last_tl0_pic_idx: u8,
+}
+
+impl StreamRewriter {
+    pub fn new(ssrc: u32, clock_rate: u32) -> Self {
+        StreamRewriter {
+            ssrc,
+            clock_rate,
+            rewrite_vp8: false,
+            source: None,
+            last_sequence_number: rand::random::<u16>(),
+            last_timestamp: rand::random::<u32>(),
+            last_arrival: None,
+            last_picture_id: rand::random::<u16>() & VP8_PICTURE_ID_MASK,
+            last_tl0_pic_idx: rand::random::<u8>(),
+        }
+    }
+
+    /// enable_vp8 also rewrites the VP8 picture id and TL0PICIDX of the payload
+    /// descriptor to keep them continuous
+    pub fn enable_vp8(&mut self) {
+        self.rewrite_vp8 = true;
+    }
+
+    /// ssrc returns the outgoing SSRC
+    pub fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    /// rewrite maps a packet received at arrival onto the outgoing stream. It
+    /// returns false, leaving the packet untouched, for a packet which must not
+    /// be forwarded: one sent by the source before the switch to it, one which
+    /// was dropped, or one later than the reorder window.
+    pub fn rewrite(&mut self, packet: &mut Packet, arrival: SystemTime) -> Result<bool> {
+        let sequence_number = packet.header.sequence_number;
+        let is_current = match &self.source {
+            Some(source) if source.ssrc == packet.header.ssrc => {
+                if is_newer(source.first_sequence_number, sequence_number)
+                    || source.dropped.contains(&sequence_number)
+                    || source.is_too_late(sequence_number)
+                {
+                    return Ok(false);
+                }
+                true
+            }
+            _ => false,
+        };
+        if !is_current {
+            self.source = Some(self.switch_source(packet, arrival)?);
+        }
+        let source = match &mut self.source {
+            Some(source) => source,
+            None => return Ok(false),
+        };
+
+        let out_sequence_number = sequence_number.wrapping_add(source.offset(sequence_number));
+        let out_timestamp = packet
+            .header
+            .timestamp
+            .wrapping_add(source.timestamp_offset);
+        let (mut picture_id, mut tl0_pic_idx) = (None, None);
+        if self.rewrite_vp8 && !packet.payload.is_empty() {
+            let (payload, id, idx) = rewrite_vp8_descriptor(&packet.payload, source)?;
+            packet.payload = payload;
+            picture_id = id;
+            tl0_pic_idx = idx;
+        }
+
+        if is_newer(sequence_number, source.highest_sequence_number) {
+            source.highest_sequence_number = sequence_number;
+            self.last_sequence_number = out_sequence_number;
+            self.last_timestamp = out_timestamp;
+            self.last_arrival = Some(arrival);
+            if let Some(picture_id) = picture_id {
+                self.last_picture_id = picture_id;
+            }
+            if let Some(tl0_pic_idx) = tl0_pic_idx {
+                self.last_tl0_pic_idx = tl0_pic_idx;
+            }
+        }
+
+        packet.header.ssrc = self.ssrc;
+        packet.header.sequence_number = out_sequence_number;
+        packet.header.timestamp = out_timestamp;
+        Ok(true)
+    }
+
+    /// drop_packet accounts for a packet of the current source which is not
+    /// forwarded, so that the next packets have no gap in sequence numbers. Late
+    /// packets before the dropped one keep the offset of their position. A
+    /// dropped packet older than the highest forwarded one leaves its gap.
+    pub fn drop_packet(&mut self, packet: &Packet) {
+        if let Some(source) = &mut self.source {
+            let sequence_number = packet.header.sequence_number;
+            if source.ssrc == packet.header.ssrc
+                && is_newer(sequence_number, source.highest_sequence_number)
+            {
+                source.highest_sequence_number = sequence_number;
+                source.sequence_number_offset = source.sequence_number_offset.wrapping_sub(1);
+                source.dropped.push_back(sequence_number);
+                while let Some(&oldest) = source.dropped.front() {
+                    if !source.is_too_late(oldest) {
+                        break;
+                    }
+                    source.dropped.pop_front();
+                }
+                self.last_sequence_number =
+                    sequence_number.wrapping_add(source.sequence_number_offset);
+            }
+        }
+    }
+
+    fn switch_source(&mut self, packet: &Packet, arrival: SystemTime) -> Result<SourceState> {
+        let sequence_number = packet.header.sequence_number;
+        // advance the timestamp by the time elapsed since the last packet
+        let elapsed = match self.last_arrival {
+            Some(last_arrival) => arrival
+                .duration_since(last_arrival)
+                .unwrap_or_default()
+                .as_nanos(),
+            None => 0,
+        };
+        let ticks = ((elapsed * self.clock_rate as u128 / 1_000_000_000) as u32).max(1);
+
+        let mut source = SourceState {
+            ssrc: packet.header.ssrc,
+            first_sequence_number: sequence_number,
+            highest_sequence_number: sequence_number.wrapping_sub(1),
+            sequence_number_offset: self
+                .last_sequence_number
+                .wrapping_add(1)
+                .wrapping_sub(sequence_number),
+            timestamp_offset: self
+                .last_timestamp
+                .wrapping_add(ticks)
+                .wrapping_sub(packet.header.timestamp),
+            ..Default::default()
+        };
+
+        if self.rewrite_vp8 && !packet.payload.is_empty() {
+            let mut vp8 = Vp8Packet::default();
+            vp8.depacketize(&packet.payload)?;
+            source.picture_id_offset = self
+                .last_picture_id
+                .wrapping_add(1)
+                .wrapping_sub(vp8.picture_id)
+                & VP8_PICTURE_ID_MASK;
+            source.tl0_pic_idx_offset = self
+                .last_tl0_pic_idx
+                .wrapping_add(1)
+                .wrapping_sub(vp8.tl0_pic_idx);
+        }
+
+        Ok(source)
+    }
+}
+
+/// rewrite_vp8_descriptor offsets the picture id and TL0PICIDX of a VP8 payload
+/// descriptor, returning the payload and the new values when present
+fn rewrite_vp8_descriptor(
+    payload: &Bytes,
+    source: &SourceState,
+) -> Result<(Bytes, Option<u16>, Option<u8>)> {
+    let mut vp8 = Vp8Packet::default();
+    vp8.depacketize(payload)?;
+
+    let mut out = BytesMut::from(&payload[..]);
+    let (mut picture_id, mut tl0_pic_idx) = (None, None);
+    // the picture id follows the required and extended control bits
+    let mut index = 2;
+    if vp8.i == 1 {
+        let id = vp8.picture_id.wrapping_add(source.picture_id_offset) & VP8_PICTURE_ID_MASK;
+        if out[index] & 0x80 != 0 {
+            out[index] = 0x80 | (id >> 8) as u8;
+            out[index + 1] = id as u8;
+            index += 2;
+        } else {
+            out[index] = id as u8 & 0x7F;
+            index += 1;
+        }
+        picture_id = Some(id);
+    }
+    if vp8.l == 1 {
+        let idx = vp8.tl0_pic_idx.wrapping_add(source.tl0_pic_idx_offset);
+        out[index] = idx;
+        tl0_pic_idx = Some(idx);
+    }
+
+    Ok((out.freeze(), picture_id, tl0_pic_idx))
+}
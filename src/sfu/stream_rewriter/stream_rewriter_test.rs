@@ -0,0 +1,179 @@
+use super::*;
+use crate::test_util::{at, packet};
+
+const PAYLOAD: &[u8] = &[0x01, 0x02];
+
+/// VP8 payload with a 15 bits picture id and a TL0PICIDX
+fn vp8_packet(ssrc: u32, sequence_number: u16, picture_id: u16, tl0_pic_idx: u8) -> Packet {
+    let payload = [
+        0x90,
+        0xC0,
+        0x80 | (picture_id >> 8) as u8,
+        picture_id as u8,
+        tl0_pic_idx,
+        0xAA,
+    ];
+    packet(ssrc, sequence_number, 0, &payload)
+}
+
+#[test]
+fn test_stream_rewriter_switch() -> Result<()> {
+    let mut rewriter = StreamRewriter::new(0xABCD, 90000);
+
+    let mut p = packet(0x1111, 100, 5000, PAYLOAD);
+    assert!(rewriter.rewrite(&mut p, at(0))?);
+    assert_eq!(p.header.ssrc, 0xABCD);
+    let (first_sequence_number, first_timestamp) = (p.header.sequence_number, p.header.timestamp);
+
+    let mut p = packet(0x1111, 101, 8000, PAYLOAD);
+    assert!(rewriter.rewrite(&mut p, at(33))?);
+    assert_eq!(
+        p.header.sequence_number,
+        first_sequence_number.wrapping_add(1)
+    );
+    assert_eq!(p.header.timestamp, first_timestamp.wrapping_add(3000));
+
+    // switching to another source 100ms later
+    let mut p = packet(0x2222, 60000, 1_000_000, PAYLOAD);
+    assert!(rewriter.rewrite(&mut p, at(133))?);
+    assert_eq!(p.header.ssrc, 0xABCD);
+    assert_eq!(
+        p.header.sequence_number,
+        first_sequence_number.wrapping_add(2)
+    );
+    assert_eq!(p.header.timestamp, first_timestamp.wrapping_add(12000));
+
+    let mut p = packet(0x2222, 60001, 1_003_000, PAYLOAD);
+    assert!(rewriter.rewrite(&mut p, at(166))?);
+    assert_eq!(
+        p.header.sequence_number,
+        first_sequence_number.wrapping_add(3)
+    );
+    assert_eq!(p.header.timestamp, first_timestamp.wrapping_add(15000));
+
+    // a loss keeps its gap so the receiver can NACK it
+    let mut p = packet(0x2222, 60003, 1_003_000, PAYLOAD);
+    assert!(rewriter.rewrite(&mut p, at(170))?);
+    assert_eq!(
+        p.header.sequence_number,
+        first_sequence_number.wrapping_add(5)
+    );
+
+    // a retransmission of the loss is mapped into the gap
+    let mut p = packet(0x2222, 60002, 1_003_000, PAYLOAD);
+    assert!(rewriter.rewrite(&mut p, at(180))?);
+    assert_eq!(
+        p.header.sequence_number,
+        first_sequence_number.wrapping_add(4)
+    );
+
+    // packets sent before the switch are not forwarded
+    let mut p = packet(0x2222, 59999, 997_000, PAYLOAD);
+    assert!(!rewriter.rewrite(&mut p, at(180))?);
+    assert_eq!(p.header.ssrc, 0x2222);
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_rewriter_drop() -> Result<()> {
+    let mut rewriter = StreamRewriter::new(0xABCD, 90000);
+
+    let mut p = packet(0x1111, 65534, 0, PAYLOAD);
+    rewriter.rewrite(&mut p, at(0))?;
+    let first_sequence_number = p.header.sequence_number;
+
+    rewriter.drop_packet(&packet(0x1111, 65535, 0, PAYLOAD));
+    rewriter.drop_packet(&packet(0x1111, 0, 0, PAYLOAD));
+
+    let mut p = packet(0x1111, 1, 0, PAYLOAD);
+    rewriter.rewrite(&mut p, at(10))?;
+    assert_eq!(
+        p.header.sequence_number,
+        first_sequence_number.wrapping_add(1)
+    );
+
+    // a dropped packet after a loss keeps the gap of the loss
+    rewriter.drop_packet(&packet(0x1111, 3, 0, PAYLOAD));
+    let mut p = packet(0x1111, 4, 0, PAYLOAD);
+    rewriter.rewrite(&mut p, at(20))?;
+    assert_eq!(
+        p.header.sequence_number,
+        first_sequence_number.wrapping_add(3)
+    );
+
+    // switching after a drop continues from the last forwarded packet
+    rewriter.drop_packet(&packet(0x1111, 5, 0, PAYLOAD));
+    let mut p = packet(0x2222, 1000, 0, PAYLOAD);
+    rewriter.rewrite(&mut p, at(30))?;
+    assert_eq!(
+        p.header.sequence_number,
+        first_sequence_number.wrapping_add(4)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_rewriter_drop_reordered() -> Result<()> {
+    let mut rewriter = StreamRewriter::new(0xABCD, 90000);
+
+    let mut p = packet(0x1111, 10, 0, PAYLOAD);
+    rewriter.rewrite(&mut p, at(0))?;
+    let first_sequence_number = p.header.sequence_number;
+
+    // 11 arrives after 12 is dropped and keeps the offset of its position
+    rewriter.drop_packet(&packet(0x1111, 12, 0, PAYLOAD));
+    let mut sequence_numbers = vec![];
+    for sequence_number in [11, 13, 14] {
+        let mut p = packet(0x1111, sequence_number, 0, PAYLOAD);
+        assert!(rewriter.rewrite(&mut p, at(10))?);
+        sequence_numbers.push(p.header.sequence_number.wrapping_sub(first_sequence_number));
+    }
+    assert_eq!(sequence_numbers, vec![1, 2, 3]);
+
+    // a dropped packet is not forwarded when it is received again
+    let mut p = packet(0x1111, 12, 0, PAYLOAD);
+    assert!(!rewriter.rewrite(&mut p, at(20))?);
+
+    // nor is a packet later than the reorder window
+    rewriter.drop_packet(&packet(0x1111, 14 + MAX_REORDER, 0, PAYLOAD));
+    let mut p = packet(0x1111, 13, 0, PAYLOAD);
+    assert!(!rewriter.rewrite(&mut p, at(30))?);
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_rewriter_vp8() -> Result<()> {
+    let mut rewriter = StreamRewriter::new(0xABCD, 90000);
+    rewriter.enable_vp8();
+
+    let mut p = vp8_packet(0x1111, 100, 0x7FFF, 255);
+    rewriter.rewrite(&mut p, at(0))?;
+    let mut vp8 = Vp8Packet::default();
+    let payload = vp8.depacketize(&p.payload)?;
+    assert_eq!(payload, Bytes::from_static(&[0xAA]));
+    let (picture_id, tl0_pic_idx) = (vp8.picture_id, vp8.tl0_pic_idx);
+
+    let mut p = vp8_packet(0x1111, 101, 0, 0);
+    rewriter.rewrite(&mut p, at(33))?;
+    vp8.depacketize(&p.payload)?;
+    assert_eq!(vp8.picture_id, picture_id.wrapping_add(1) & 0x7FFF);
+    assert_eq!(vp8.tl0_pic_idx, tl0_pic_idx.wrapping_add(1));
+
+    // another publisher with unrelated picture ids
+    let mut p = vp8_packet(0x2222, 5000, 1234, 77);
+    rewriter.rewrite(&mut p, at(66))?;
+    vp8.depacketize(&p.payload)?;
+    assert_eq!(vp8.picture_id, picture_id.wrapping_add(2) & 0x7FFF);
+    assert_eq!(vp8.tl0_pic_idx, tl0_pic_idx.wrapping_add(2));
+
+    let mut p = vp8_packet(0x2222, 5001, 1235, 77);
+    rewriter.rewrite(&mut p, at(99))?;
+    vp8.depacketize(&p.payload)?;
+    assert_eq!(vp8.picture_id, picture_id.wrapping_add(3) & 0x7FFF);
+    assert_eq!(vp8.tl0_pic_idx, tl0_pic_idx.wrapping_add(2));
+
+    Ok(())
+}
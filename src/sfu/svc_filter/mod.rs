@@ -0,0 +1,400 @@
+#[cfg(test)]
+mod svc_filter_test;
+
+use crate::codecs::vp9::Vp9Packet;
+use crate::error::Result;
+use crate::extension::dependency_descriptor_extension::{
+    DecodeTargetIndication, DependencyDescriptor,
+};
+use crate::packet::Packet;
+use crate::packetizer::Depacketizer;
+
+use bytes::{Bytes, BytesMut};
+use std::collections::VecDeque;
+
+const VP9_PICTURE_ID_MASK: u16 = 0x7FFF;
+const VP9_SHORT_PICTURE_ID_MASK: u16 = 0x7F;
+/// P_DIFF reaches at most this many pictures back, dropped pictures older than
+/// that no longer affect the rewritten references
+const VP9_MAX_PICTURE_DIFF: u16 = 0x7F;
+
+/// SvcLayer is the scalability information of a packet of an SVC stream
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SvcLayer {
+    pub spatial_id: u8,
+    pub temporal_id: u8,
+    /// the frame is the base layer of a keyframe, the higher spatial layers of
+    /// the picture only use inter-layer prediction
+    pub keyframe: bool,
+    /// the packet is the first of the frame of its spatial layer
+    pub start_of_frame: bool,
+    /// the packet is the last of the frame of its spatial layer
+    pub end_of_frame: bool,
+    /// the frame does not depend on previous pictures of its spatial layer, so
+    /// forwarding can switch up to its spatial layer
+    pub spatial_switching_point: bool,
+    /// the frame only depends on lower temporal layers, so forwarding can
+    /// switch up to its temporal layer
+    pub temporal_switching_point: bool,
+}
+
+impl SvcLayer {
+    /// from_vp9 reads the layer of a VP9 payload descriptor. A frame which is
+    /// not inter-picture predicted is a switching point for both layers.
+    pub fn from_vp9(vp9: &Vp9Packet) -> Self {
+        SvcLayer {
+            spatial_id: vp9.sid,
+            temporal_id: vp9.tid,
+            keyframe: !vp9.p && vp9.sid == 0,
+            start_of_frame: vp9.b,
+            end_of_frame: vp9.e,
+            spatial_switching_point: !vp9.p,
+            temporal_switching_point: !vp9.p || vp9.u,
+        }
+    }
+
+    /// from_dependency_descriptor reads the layer of an AV1 packet from its
+    /// Dependency Descriptor. A base layer frame carrying the template structure
+    /// is a keyframe, a frame which is a switch for any of the decode targets a
+    /// switching point.
+    pub fn from_dependency_descriptor(descriptor: &DependencyDescriptor) -> Self {
+        let dependencies = &descriptor.frame_dependencies;
+        let keyframe = descriptor.attached_structure.is_some() && dependencies.spatial_id == 0;
+        let switching_point = keyframe
+            || dependencies
+                .decode_target_indications
+                .contains(&DecodeTargetIndication::Switch);
+        SvcLayer {
+            spatial_id: dependencies.spatial_id,
+            temporal_id: dependencies.temporal_id,
+            keyframe,
+            start_of_frame: descriptor.start_of_frame,
+            end_of_frame: descriptor.end_of_frame,
+            spatial_switching_point: switching_point,
+            temporal_switching_point: switching_point,
+        }
+    }
+}
+
+/// PictureLayers holds the layers forwarded for a picture
+#[derive(Debug, Copy, Clone)]
+struct PictureLayers {
+    timestamp: u32,
+    spatial_id: Option<u8>,
+    temporal_id: u8,
+}
+
+impl PictureLayers {
+    /// forwards_picture reports whether any frame of the picture of a packet is
+    /// forwarded. All spatial layer frames of a picture share its temporal layer.
+    fn forwards_picture(&self, layer: &SvcLayer) -> bool {
+        self.spatial_id.is_some() && layer.temporal_id <= self.temporal_id
+    }
+
+    /// forward reports whether the packet belongs to the forwarded layers,
+    /// setting the marker bit at the end of the highest spatial layer frame
+    fn forward(&self, packet: &mut Packet, layer: &SvcLayer) -> bool {
+        let spatial_id = match self.spatial_id {
+            Some(spatial_id) => spatial_id,
+            None => return false,
+        };
+        if layer.spatial_id > spatial_id || layer.temporal_id > self.temporal_id {
+            return false;
+        }
+
+        if layer.end_of_frame && layer.spatial_id == spatial_id {
+            packet.header.marker = true;
+        }
+        true
+    }
+}
+
+/// PictureIds maps the picture ids of forwarded VP9 pictures onto continuous
+/// ones, skipping the pictures which were dropped as a whole
+#[derive(Debug, Default, Clone)]
+struct PictureIds {
+    highest: Option<u16>,
+    /// picture id offset of pictures after the last dropped one
+    offset: u16,
+    /// dropped picture ids within VP9_MAX_PICTURE_DIFF of the highest one
+    dropped: VecDeque<u16>,
+}
+
+impl PictureIds {
+    /// is_newer reports whether picture id a comes after b, accounting for wrap
+    fn is_newer(a: u16, b: u16, mask: u16) -> bool {
+        let diff = a.wrapping_sub(b) & mask;
+        diff != 0 && diff <= mask / 2
+    }
+
+    /// advance records a picture id, reporting whether it is newer than all
+    /// previous ones
+    fn advance(&mut self, picture_id: u16, mask: u16) -> bool {
+        match self.highest {
+            Some(highest) if !PictureIds::is_newer(picture_id, highest, mask) => false,
+            _ => {
+                self.highest = Some(picture_id);
+                true
+            }
+        }
+    }
+
+    /// drop_picture skips a picture, lowering the ids of the following ones
+    fn drop_picture(&mut self, picture_id: u16, mask: u16) {
+        if !self.advance(picture_id, mask) {
+            return;
+        }
+        self.offset = self.offset.wrapping_sub(1);
+        self.dropped.push_back(picture_id);
+        let window = VP9_MAX_PICTURE_DIFF.min(mask / 2);
+        while let Some(&oldest) = self.dropped.front() {
+            if !PictureIds::is_newer(picture_id.wrapping_sub(window), oldest, mask) {
+                break;
+            }
+            self.dropped.pop_front();
+        }
+    }
+
+    /// dropped_between returns the number of dropped pictures after from and
+    /// up to to
+    fn dropped_between(&self, from: u16, to: u16, mask: u16) -> u16 {
+        self.dropped
+            .iter()
+            .filter(|&&dropped| {
+                PictureIds::is_newer(dropped, from, mask)
+                    && !PictureIds::is_newer(dropped, to, mask)
+            })
+            .count() as u16
+    }
+
+    /// rewrite returns the continuous picture id of a forwarded picture, each
+    /// drop after it lowering the latest offset by one
+    fn rewrite(&mut self, picture_id: u16, mask: u16) -> u16 {
+        self.advance(picture_id, mask);
+        let later_drops = self
+            .dropped
+            .iter()
+            .filter(|&&dropped| PictureIds::is_newer(dropped, picture_id, mask))
+            .count() as u16;
+        picture_id
+            .wrapping_add(self.offset)
+            .wrapping_add(later_drops)
+            & mask
+    }
+}
+
+/// SvcFilter thins a VP9 or AV1 SVC stream down to target spatial and temporal
+/// layers for one subscriber.
+///
+/// Forwarding starts at a keyframe, which switches up to the target layers.
+/// Switching down happens at the next picture, switching up otherwise at the
+/// next switching point of the higher layer. Temporal layers are selected per
+/// picture, so all spatial layer frames of a picture are kept or dropped
+/// together. The VP9 picture ids of forwarded pictures are rewritten to stay
+/// continuous, along with the flexible mode references to previous pictures.
+/// TL0PICIDX needs no rewrite as base temporal layer pictures are always
+/// forwarded. The marker bit is set on the end of the frame of the highest
+/// forwarded spatial layer. Late packets of the previous picture get its
+/// decision, older ones are not forwarded.
+#[derive(Debug, Clone)]
+pub struct SvcFilter {
+    target_spatial_id: u8,
+    target_temporal_id: u8,
+    current_spatial_id: Option<u8>,
+    current_temporal_id: u8,
+    picture_timestamp: Option<u32>,
+    previous_picture: Option<PictureLayers>,
+    picture_ids: PictureIds,
+}
+
+impl Default for SvcFilter {
+    fn default() -> Self {
+        SvcFilter::new()
+    }
+}
+
+impl SvcFilter {
+    /// new creates a filter forwarding all layers
+    pub fn new() -> Self {
+        SvcFilter {
+            target_spatial_id: u8::MAX,
+            target_temporal_id: u8::MAX,
+            current_spatial_id: None,
+            current_temporal_id: u8::MAX,
+            picture_timestamp: None,
+            previous_picture: None,
+            picture_ids: PictureIds::default(),
+        }
+    }
+
+    /// set_target_spatial_layer selects the highest spatial layer to forward
+    pub fn set_target_spatial_layer(&mut self, spatial_id: u8) {
+        self.target_spatial_id = spatial_id;
+    }
+
+    /// set_target_temporal_layer selects the highest temporal layer to forward
+    pub fn set_target_temporal_layer(&mut self, temporal_id: u8) {
+        self.target_temporal_id = temporal_id;
+    }
+
+    /// current_spatial_layer returns the highest forwarded spatial layer, None
+    /// until the first keyframe
+    pub fn current_spatial_layer(&self) -> Option<u8> {
+        self.current_spatial_id
+    }
+
+    /// current_temporal_layer returns the highest forwarded temporal layer
+    pub fn current_temporal_layer(&self) -> u8 {
+        self.current_temporal_id
+    }
+
+    /// filter_vp9 decides whether to forward a VP9 packet, rewriting the
+    /// picture id and references of a forwarded one. Packets without payload,
+    /// e.g. padding, are not forwarded.
+    pub fn filter_vp9(&mut self, packet: &mut Packet) -> Result<bool> {
+        if packet.payload.is_empty() {
+            return Ok(false);
+        }
+
+        let mut vp9 = Vp9Packet::default();
+        vp9.depacketize(&packet.payload)?;
+        let layer = SvcLayer::from_vp9(&vp9);
+        let picture = match self.select(packet.header.timestamp, &layer) {
+            Some(picture) => picture,
+            None => return Ok(false),
+        };
+
+        if !picture.forward(packet, &layer) {
+            if vp9.i && !picture.forwards_picture(&layer) {
+                let mask = vp9_picture_id_mask(&packet.payload);
+                self.picture_ids.drop_picture(vp9.picture_id, mask);
+            }
+            return Ok(false);
+        }
+
+        if vp9.i {
+            packet.payload = rewrite_vp9_descriptor(&packet.payload, &vp9, &mut self.picture_ids);
+        }
+        Ok(true)
+    }
+
+    /// filter_av1 decides whether to forward an AV1 packet given its Dependency
+    /// Descriptor
+    pub fn filter_av1(&mut self, packet: &mut Packet, descriptor: &DependencyDescriptor) -> bool {
+        self.filter(packet, &SvcLayer::from_dependency_descriptor(descriptor))
+    }
+
+    /// filter decides whether to forward a packet of the given layer, setting its
+    /// marker bit if it ends the picture for the subscriber. Packets which are not
+    /// forwarded should be skipped with StreamRewriter::drop_packet.
+    pub fn filter(&mut self, packet: &mut Packet, layer: &SvcLayer) -> bool {
+        match self.select(packet.header.timestamp, layer) {
+            Some(picture) => picture.forward(packet, layer),
+            None => false,
+        }
+    }
+
+    /// select returns the layers forwarded for the picture of a packet, None for
+    /// a late packet older than the previous picture
+    fn select(&mut self, timestamp: u32, layer: &SvcLayer) -> Option<PictureLayers> {
+        match self.picture_timestamp {
+            Some(picture_timestamp) if picture_timestamp == timestamp => {}
+            // late packets keep the decision of their picture
+            Some(picture_timestamp) if (timestamp.wrapping_sub(picture_timestamp) as i32) < 0 => {
+                return match self.previous_picture {
+                    Some(previous) if previous.timestamp == timestamp => Some(previous),
+                    _ => None,
+                };
+            }
+            picture_timestamp => {
+                self.previous_picture = picture_timestamp.map(|timestamp| PictureLayers {
+                    timestamp,
+                    spatial_id: self.current_spatial_id,
+                    temporal_id: self.current_temporal_id,
+                });
+                self.picture_timestamp = Some(timestamp);
+                self.start_picture(layer);
+            }
+        }
+
+        if layer.keyframe && layer.start_of_frame {
+            self.current_spatial_id = Some(self.target_spatial_id);
+            self.current_temporal_id = self.target_temporal_id;
+        } else if let Some(current_spatial_id) = &mut self.current_spatial_id {
+            if layer.spatial_id == current_spatial_id.saturating_add(1)
+                && layer.spatial_id <= self.target_spatial_id
+                && layer.start_of_frame
+                && layer.spatial_switching_point
+            {
+                *current_spatial_id = layer.spatial_id;
+            }
+        }
+
+        Some(PictureLayers {
+            timestamp,
+            spatial_id: self.current_spatial_id,
+            temporal_id: self.current_temporal_id,
+        })
+    }
+
+    /// start_picture switches layers down to the targets, and the temporal layer
+    /// up at a switching point
+    fn start_picture(&mut self, layer: &SvcLayer) {
+        if let Some(current_spatial_id) = &mut self.current_spatial_id {
+            *current_spatial_id = (*current_spatial_id).min(self.target_spatial_id);
+        }
+
+        if self.target_temporal_id < self.current_temporal_id {
+            self.current_temporal_id = self.target_temporal_id;
+        } else if layer.temporal_id > self.current_temporal_id
+            && layer.temporal_id <= self.target_temporal_id
+            && layer.temporal_switching_point
+        {
+            self.current_temporal_id = layer.temporal_id;
+        }
+    }
+}
+
+/// vp9_picture_id_mask returns the mask of the 15 or 7 bits picture id of a VP9
+/// payload descriptor carrying a picture id
+fn vp9_picture_id_mask(payload: &Bytes) -> u16 {
+    if payload[1] & 0x80 != 0 {
+        VP9_PICTURE_ID_MASK
+    } else {
+        VP9_SHORT_PICTURE_ID_MASK
+    }
+}
+
+/// rewrite_vp9_descriptor maps the picture id of a VP9 payload descriptor and,
+/// in flexible mode, its references onto the continuous picture ids
+fn rewrite_vp9_descriptor(payload: &Bytes, vp9: &Vp9Packet, picture_ids: &mut PictureIds) -> Bytes {
+    let mask = vp9_picture_id_mask(payload);
+    let picture_id = picture_ids.rewrite(vp9.picture_id, mask);
+
+    let mut out = BytesMut::from(&payload[..]);
+    // the picture id follows the required octet
+    let mut index = 1;
+    if mask == VP9_PICTURE_ID_MASK {
+        out[index] = 0x80 | (picture_id >> 8) as u8;
+        out[index + 1] = picture_id as u8;
+        index += 2;
+    } else {
+        out[index] = picture_id as u8;
+        index += 1;
+    }
+    if vp9.l {
+        // TL0PICIDX follows the layer indices in non-flexible mode
+        index += if vp9.f { 1 } else { 2 };
+    }
+    if vp9.f && vp9.p {
+        // references skip the dropped pictures between them and the picture
+        for (i, &p_diff) in vp9.pdiff.iter().enumerate() {
+            let reference = vp9.picture_id.wrapping_sub(p_diff as u16) & mask;
+            let dropped = picture_ids.dropped_between(reference, vp9.picture_id, mask);
+            let p_diff = (p_diff as u16).saturating_sub(dropped).max(1) as u8;
+            out[index + i] = p_diff << 1 | (out[index + i] & 0x01);
+        }
+    }
+
+    out.freeze()
+}
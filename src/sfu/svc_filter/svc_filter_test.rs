@@ -0,0 +1,224 @@
+use super::*;
+use crate::extension::dependency_descriptor_extension::FrameDependencyTemplate;
+use crate::header::Header;
+
+use bytes::Bytes;
+
+/// VP9 flexible mode packet carrying a whole layer frame, inter-picture
+/// predicted frames reference the previous picture
+fn vp9_packet(timestamp: u32, picture_id: u16, sid: u8, tid: u8, p: bool, u: bool) -> Packet {
+    let mut payload = vec![
+        0xBC | if p { 0x40 } else { 0x00 },
+        0x80 | (picture_id >> 8) as u8,
+        picture_id as u8,
+        tid << 5 | if u { 0x10 } else { 0x00 } | sid << 1 | if sid > 0 { 0x01 } else { 0x00 },
+    ];
+    if p {
+        payload.push(0x02);
+    }
+    payload.push(0xAA);
+
+    Packet {
+        header: Header {
+            version: 2,
+            timestamp,
+            ..Default::default()
+        },
+        payload: Bytes::from(payload),
+        ..Default::default()
+    }
+}
+
+/// filter_picture filters the three spatial layers of a picture, returning
+/// the forwarding decision and marker bit of each
+fn filter_picture(
+    filter: &mut SvcFilter,
+    timestamp: u32,
+    tid: u8,
+    p: [bool; 3],
+    u: bool,
+) -> Result<Vec<(bool, bool)>> {
+    let mut result = vec![];
+    for sid in 0..3 {
+        let mut packet = vp9_packet(timestamp, timestamp as u16, sid, tid, p[sid as usize], u);
+        packet.header.marker = sid == 2;
+        let forward = filter.filter_vp9(&mut packet)?;
+        result.push((forward, packet.header.marker));
+    }
+    Ok(result)
+}
+
+const KEYFRAME: [bool; 3] = [false, false, false];
+const DELTA: [bool; 3] = [true, true, true];
+
+#[test]
+fn test_svc_filter_spatial_layers() -> Result<()> {
+    let mut filter = SvcFilter::new();
+    filter.set_target_spatial_layer(1);
+
+    // waits for a keyframe
+    assert_eq!(
+        filter_picture(&mut filter, 1, 0, DELTA, false)?,
+        vec![(false, false), (false, false), (false, true)]
+    );
+    assert_eq!(filter.current_spatial_layer(), None);
+
+    let top_is_1 = vec![(true, false), (true, true), (false, true)];
+    assert_eq!(
+        filter_picture(&mut filter, 2, 0, KEYFRAME, false)?,
+        top_is_1
+    );
+    assert_eq!(filter.current_spatial_layer(), Some(1));
+    assert_eq!(filter_picture(&mut filter, 3, 0, DELTA, false)?, top_is_1);
+
+    // switching down happens at the next picture
+    filter.set_target_spatial_layer(0);
+    let top_is_0 = vec![(true, true), (false, false), (false, true)];
+    assert_eq!(filter_picture(&mut filter, 4, 0, DELTA, false)?, top_is_0);
+
+    // switching up waits for a frame without inter-picture prediction
+    filter.set_target_spatial_layer(2);
+    assert_eq!(filter_picture(&mut filter, 5, 0, DELTA, false)?, top_is_0);
+    assert_eq!(
+        filter_picture(&mut filter, 6, 0, [true, false, true], false)?,
+        vec![(true, true), (true, true), (false, true)]
+    );
+    assert_eq!(
+        filter_picture(&mut filter, 7, 0, [true, true, false], false)?,
+        vec![(true, false), (true, true), (true, true)]
+    );
+    assert_eq!(filter.current_spatial_layer(), Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn test_svc_filter_temporal_layers() -> Result<()> {
+    let mut filter = SvcFilter::new();
+    filter.set_target_temporal_layer(0);
+
+    let forwarded = vec![(true, false), (true, false), (true, true)];
+    let dropped = vec![(false, false), (false, false), (false, true)];
+    assert_eq!(
+        filter_picture(&mut filter, 1, 0, KEYFRAME, false)?,
+        forwarded
+    );
+    assert_eq!(filter_picture(&mut filter, 2, 1, DELTA, true)?, dropped);
+    assert_eq!(filter_picture(&mut filter, 3, 0, DELTA, false)?, forwarded);
+
+    // switching up waits for a switching point
+    filter.set_target_temporal_layer(1);
+    assert_eq!(filter_picture(&mut filter, 4, 1, DELTA, false)?, dropped);
+    assert_eq!(filter_picture(&mut filter, 5, 0, DELTA, false)?, forwarded);
+    assert_eq!(filter_picture(&mut filter, 6, 1, DELTA, true)?, forwarded);
+    assert_eq!(filter.current_temporal_layer(), 1);
+
+    // late packets keep the decision of their picture
+    let mut packet = vp9_packet(5, 5, 1, 0, true, false);
+    assert!(filter.filter_vp9(&mut packet)?);
+    let mut packet = vp9_packet(4, 4, 0, 1, true, false);
+    assert!(!filter.filter_vp9(&mut packet)?);
+
+    // switching down happens at the next picture
+    filter.set_target_temporal_layer(0);
+    assert_eq!(filter_picture(&mut filter, 7, 1, DELTA, true)?, dropped);
+    assert_eq!(filter.current_temporal_layer(), 0);
+
+    // picture ids skip the dropped pictures 2, 4 and 7
+    let mut packet = vp9_packet(8, 8, 0, 0, true, false);
+    assert!(filter.filter_vp9(&mut packet)?);
+    let mut vp9 = Vp9Packet::default();
+    vp9.depacketize(&packet.payload)?;
+    assert_eq!(vp9.picture_id, 5);
+
+    Ok(())
+}
+
+#[test]
+fn test_svc_filter_picture_ids() -> Result<()> {
+    let mut filter = SvcFilter::new();
+    filter.set_target_temporal_layer(0);
+
+    // L1T2 across the picture id wrap, base layer pictures reference the
+    // previous base layer picture, upper layer ones the previous picture
+    let pictures: Vec<(u16, u8, Option<u8>)> = vec![
+        (0x7FFE, 0, None),
+        (0x7FFF, 1, Some(1)),
+        (0x0000, 0, Some(2)),
+        (0x0001, 1, Some(1)),
+        (0x0002, 0, Some(2)),
+    ];
+
+    let mut forwarded = vec![];
+    for (timestamp, (picture_id, tid, p_diff)) in pictures.into_iter().enumerate() {
+        let mut packet = vp9_packet(
+            timestamp as u32,
+            picture_id,
+            0,
+            tid,
+            p_diff.is_some(),
+            false,
+        );
+        if let Some(p_diff) = p_diff {
+            packet.payload = {
+                let mut payload = packet.payload.to_vec();
+                payload[4] = p_diff << 1;
+                Bytes::from(payload)
+            };
+        }
+        if filter.filter_vp9(&mut packet)? {
+            let mut vp9 = Vp9Packet::default();
+            vp9.depacketize(&packet.payload)?;
+            forwarded.push((vp9.picture_id, vp9.pdiff));
+        }
+    }
+
+    assert_eq!(
+        forwarded,
+        vec![(0x7FFE, vec![]), (0x7FFF, vec![1]), (0x0000, vec![1])]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_svc_filter_av1() -> Result<()> {
+    let descriptor = |spatial_id: u8, keyframe: bool| DependencyDescriptor {
+        start_of_frame: true,
+        end_of_frame: true,
+        frame_dependencies: FrameDependencyTemplate {
+            spatial_id,
+            decode_target_indications: vec![DecodeTargetIndication::Required; 2],
+            ..Default::default()
+        },
+        attached_structure: if keyframe {
+            Some(Default::default())
+        } else {
+            None
+        },
+        ..Default::default()
+    };
+
+    let mut filter = SvcFilter::new();
+    filter.set_target_spatial_layer(0);
+
+    let mut packet = vp9_packet(1, 0, 0, 0, false, false);
+    assert!(!filter.filter_av1(&mut packet, &descriptor(0, false)));
+    assert!(filter.filter_av1(&mut packet, &descriptor(0, true)));
+    assert!(packet.header.marker);
+
+    let mut packet = vp9_packet(1, 0, 0, 0, false, false);
+    assert!(!filter.filter_av1(&mut packet, &descriptor(1, false)));
+    assert!(!packet.header.marker);
+
+    Ok(())
+}
+
+#[test]
+fn test_svc_filter_padding() -> Result<()> {
+    let mut filter = SvcFilter::new();
+    let mut packet = Packet::default();
+    assert!(!filter.filter_vp9(&mut packet)?);
+
+    Ok(())
+}
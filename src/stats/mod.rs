@@ -0,0 +1,2 @@
+pub mod receiver_stats;
+pub mod sender_stats;
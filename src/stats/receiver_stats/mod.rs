@@ -0,0 +1,253 @@
+#[cfg(test)]
+mod receiver_stats_test;
+
+use crate::header::Header;
+
+use std::time::SystemTime;
+
+/// number of sequential packets required before a source is valid
+pub const MIN_SEQUENTIAL: u32 = 2;
+/// largest forward jump in sequence numbers accepted without resyncing
+pub const MAX_DROPOUT: u32 = 3000;
+/// largest backward jump in sequence numbers considered reordering
+pub const MAX_MISORDER: u32 = 100;
+const RTP_SEQ_MOD: u32 = 1 << 16;
+
+/// cumulative number of packets lost is a 24 bits signed value in report blocks
+const CUMULATIVE_LOST_MAX: i64 = 0x7FFFFF;
+const CUMULATIVE_LOST_MIN: i64 = -0x800000;
+
+/// ReceptionReport holds the values of an RTCP reception report block
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ReceptionReport {
+    pub ssrc: u32,
+    /// fraction of packets lost since the previous report, in 1/256
+    pub fraction_lost: u8,
+    /// packets lost since the beginning of reception, clamped to 24 bits
+    pub total_lost: i32,
+    /// highest sequence number received, extended with the number of cycles
+    pub last_sequence_number: u32,
+    /// interarrival jitter in timestamp units
+    pub jitter: u32,
+    /// middle 32 bits of the NTP timestamp of the last sender report
+    pub last_sender_report: u32,
+    /// delay since the last sender report, in 1/65536 seconds
+    pub delay: u32,
+}
+
+/// ReceiverStats tracks the reception of an RTP source, following the
+/// algorithms of RFC 3550 Appendix A
+#[derive(Debug, Clone)]
+pub struct ReceiverStats {
+    ssrc: u32,
+    clock_rate: u32,
+
+    started: bool,
+    max_seq: u16,
+    cycles: u32,
+    base_seq: u32,
+    bad_seq: u32,
+    probation: u32,
+    received: u64,
+    expected_prior: u64,
+    received_prior: u64,
+
+    first_arrival: Option<SystemTime>,
+    last_transit: Option<u32>,
+    jitter: f64,
+
+    last_sender_report: u32,
+    last_sender_report_arrival: Option<SystemTime>,
+}
+
+impl ReceiverStats {
+    pub fn new(ssrc: u32, clock_rate: u32) -> Self {
+        ReceiverStats {
+            ssrc,
+            clock_rate,
+            started: false,
+            max_seq: 0,
+            cycles: 0,
+            base_seq: 0,
+            bad_seq: RTP_SEQ_MOD + 1,
+            probation: MIN_SEQUENTIAL,
+            received: 0,
+            expected_prior: 0,
+            received_prior: 0,
+            first_arrival: None,
+            last_transit: None,
+            jitter: 0.0,
+            last_sender_report: 0,
+            last_sender_report_arrival: None,
+        }
+    }
+
+    pub fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    /// update records a packet of the source received at arrival. It returns
+    /// false when the packet is not counted: another SSRC, a source still on
+    /// probation, or a large jump in sequence numbers awaiting confirmation.
+    pub fn update(&mut self, header: &Header, arrival: SystemTime) -> bool {
+        if header.ssrc != self.ssrc || !self.update_seq(header.sequence_number) {
+            return false;
+        }
+        self.update_jitter(header.timestamp, arrival);
+        true
+    }
+
+    /// on_sender_report records the NTP timestamp of a sender report of the
+    /// source, received at arrival
+    pub fn on_sender_report(&mut self, ntp_time: u64, arrival: SystemTime) {
+        self.last_sender_report = (ntp_time >> 16) as u32;
+        self.last_sender_report_arrival = Some(arrival);
+    }
+
+    /// packets_received returns the number of packets counted
+    pub fn packets_received(&self) -> u64 {
+        self.received
+    }
+
+    /// extended_highest_sequence returns the highest sequence number received,
+    /// extended with the number of cycles
+    pub fn extended_highest_sequence(&self) -> u32 {
+        self.cycles.wrapping_add(self.max_seq as u32)
+    }
+
+    /// packets_expected returns the number of packets expected since the first one
+    pub fn packets_expected(&self) -> u64 {
+        if !self.started || self.probation > 0 {
+            return 0;
+        }
+        (self.cycles as u64 + self.max_seq as u64 + 1).saturating_sub(self.base_seq as u64)
+    }
+
+    /// cumulative_lost returns the number of packets lost, negative when
+    /// duplicates were received
+    pub fn cumulative_lost(&self) -> i64 {
+        self.packets_expected() as i64 - self.received as i64
+    }
+
+    /// jitter returns the interarrival jitter in timestamp units
+    pub fn jitter(&self) -> u32 {
+        self.jitter as u32
+    }
+
+    /// report returns the reception report block of the source at now, and
+    /// starts a new interval for the fraction lost
+    pub fn report(&mut self, now: SystemTime) -> ReceptionReport {
+        let expected = self.packets_expected();
+        let expected_interval = expected - self.expected_prior;
+        self.expected_prior = expected;
+        let received_interval = self.received - self.received_prior;
+        self.received_prior = self.received;
+
+        let lost_interval = expected_interval as i64 - received_interval as i64;
+        let fraction_lost = if expected_interval == 0 || lost_interval <= 0 {
+            0
+        } else {
+            ((lost_interval << 8) / expected_interval as i64) as u8
+        };
+
+        let delay = match self.last_sender_report_arrival {
+            Some(arrival) => now
+                .duration_since(arrival)
+                .map(|d| (d.as_nanos() * 65536 / 1_000_000_000) as u32)
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        ReceptionReport {
+            ssrc: self.ssrc,
+            fraction_lost,
+            total_lost: self
+                .cumulative_lost()
+                .clamp(CUMULATIVE_LOST_MIN, CUMULATIVE_LOST_MAX) as i32,
+            last_sequence_number: self.extended_highest_sequence(),
+            jitter: self.jitter(),
+            last_sender_report: self.last_sender_report,
+            delay,
+        }
+    }
+
+    fn init_seq(&mut self, seq: u16) {
+        self.base_seq = seq as u32;
+        self.max_seq = seq;
+        self.bad_seq = RTP_SEQ_MOD + 1;
+        self.cycles = 0;
+        self.received = 0;
+        self.received_prior = 0;
+        self.expected_prior = 0;
+    }
+
+    /// update_seq implements RFC 3550 A.1
+    fn update_seq(&mut self, seq: u16) -> bool {
+        if !self.started {
+            // first packet of the source, which is on probation until
+            // MIN_SEQUENTIAL packets are received in sequence
+            self.started = true;
+            self.init_seq(seq);
+            self.max_seq = seq.wrapping_sub(1);
+            self.probation = MIN_SEQUENTIAL;
+        }
+
+        let udelta = seq.wrapping_sub(self.max_seq) as u32;
+
+        if self.probation > 0 {
+            // packet is in sequence
+            if seq == self.max_seq.wrapping_add(1) {
+                self.probation -= 1;
+                self.max_seq = seq;
+                if self.probation == 0 {
+                    self.init_seq(seq);
+                    self.received += 1;
+                    return true;
+                }
+            } else {
+                self.probation = MIN_SEQUENTIAL - 1;
+                self.max_seq = seq;
+            }
+            return false;
+        } else if udelta < MAX_DROPOUT {
+            // in order, with permissible gap
+            if seq < self.max_seq {
+                // sequence number wrapped, count another 64K cycle
+                self.cycles = self.cycles.wrapping_add(RTP_SEQ_MOD);
+            }
+            self.max_seq = seq;
+        } else if udelta <= RTP_SEQ_MOD - MAX_MISORDER {
+            // the sequence number made a very large jump
+            if seq as u32 == self.bad_seq {
+                // two sequential packets, assume that the other side restarted
+                // without telling us so just re-sync
+                self.init_seq(seq);
+            } else {
+                self.bad_seq = (seq as u32 + 1) & (RTP_SEQ_MOD - 1);
+                return false;
+            }
+        } else {
+            // duplicate or reordered packet
+        }
+        self.received += 1;
+        true
+    }
+
+    /// update_jitter implements RFC 3550 A.8
+    fn update_jitter(&mut self, timestamp: u32, arrival: SystemTime) {
+        let first_arrival = *self.first_arrival.get_or_insert(arrival);
+        let elapsed = arrival
+            .duration_since(first_arrival)
+            .unwrap_or_default()
+            .as_nanos();
+        // arrival time in timestamp units, wrapping like RTP timestamps
+        let arrival = (elapsed * self.clock_rate as u128 / 1_000_000_000) as u32;
+
+        let transit = arrival.wrapping_sub(timestamp);
+        if let Some(last_transit) = self.last_transit {
+            let d = (transit.wrapping_sub(last_transit) as i32).unsigned_abs() as f64;
+            self.jitter += (d - self.jitter) / 16.0;
+        }
+        self.last_transit = Some(transit);
+    }
+}
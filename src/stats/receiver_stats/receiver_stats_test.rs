@@ -0,0 +1,127 @@
+use super::*;
+use crate::test_util::{at, packet};
+
+fn header(sequence_number: u16, timestamp: u32) -> Header {
+    packet(0x1234ABCD, sequence_number, timestamp, &[]).header
+}
+
+#[test]
+fn test_receiver_stats_probation() {
+    let mut stats = ReceiverStats::new(0x1234ABCD, 90000);
+
+    assert!(!stats.update(&header(100, 0), at(0)));
+    assert_eq!(stats.packets_received(), 0);
+    assert!(stats.update(&header(101, 0), at(0)));
+    assert_eq!(stats.packets_received(), 1);
+    assert_eq!(stats.extended_highest_sequence(), 101);
+
+    // another SSRC is ignored
+    let mut other = header(102, 0);
+    other.ssrc = 0x5678ABCD;
+    assert!(!stats.update(&other, at(0)));
+    assert_eq!(stats.packets_received(), 1);
+
+    // out of sequence packets restart probation
+    let mut stats = ReceiverStats::new(0x1234ABCD, 90000);
+    assert!(!stats.update(&header(100, 0), at(0)));
+    assert!(!stats.update(&header(200, 0), at(0)));
+    assert!(stats.update(&header(201, 0), at(0)));
+    assert_eq!(stats.extended_highest_sequence(), 201);
+}
+
+#[test]
+fn test_receiver_stats_loss() {
+    let mut stats = ReceiverStats::new(0x1234ABCD, 90000);
+    for seq in [65530u16, 65531, 65532, 65534, 65535, 1, 2, 2] {
+        stats.update(&header(seq, 0), at(0));
+    }
+
+    // 65531..=2 expected, 65533 and 0 lost, 2 duplicated
+    assert_eq!(stats.extended_highest_sequence(), (1 << 16) + 2);
+    assert_eq!(stats.packets_expected(), 8);
+    assert_eq!(stats.packets_received(), 7);
+    assert_eq!(stats.cumulative_lost(), 1);
+
+    let report = stats.report(at(0));
+    assert_eq!(report.ssrc, 0x1234ABCD);
+    assert_eq!(report.total_lost, 1);
+    assert_eq!(report.fraction_lost, 32);
+    assert_eq!(report.last_sequence_number, (1 << 16) + 2);
+
+    // fraction lost is computed over the interval since the previous report
+    for seq in 3..=6u16 {
+        stats.update(&header(seq, 0), at(0));
+    }
+    let report = stats.report(at(0));
+    assert_eq!(report.fraction_lost, 0);
+    assert_eq!(report.total_lost, 1);
+
+    for seq in [8u16, 10] {
+        stats.update(&header(seq, 0), at(0));
+    }
+    let report = stats.report(at(0));
+    assert_eq!(report.fraction_lost, 128);
+    assert_eq!(report.total_lost, 3);
+}
+
+#[test]
+fn test_receiver_stats_resync() {
+    let mut stats = ReceiverStats::new(0x1234ABCD, 90000);
+    for seq in 10..20u16 {
+        stats.update(&header(seq, 0), at(0));
+    }
+    assert_eq!(stats.packets_received(), 9);
+
+    // a single very large jump is dropped
+    assert!(!stats.update(&header(30000, 0), at(0)));
+    assert_eq!(stats.extended_highest_sequence(), 19);
+
+    // a second sequential packet after the jump resyncs the source
+    assert!(stats.update(&header(30001, 0), at(0)));
+    assert_eq!(stats.extended_highest_sequence(), 30001);
+    assert_eq!(stats.packets_received(), 1);
+    assert_eq!(stats.cumulative_lost(), 0);
+
+    // reordered packets are counted without moving the highest sequence
+    assert!(stats.update(&header(30003, 0), at(0)));
+    assert!(stats.update(&header(30002, 0), at(0)));
+    assert_eq!(stats.extended_highest_sequence(), 30003);
+    assert_eq!(stats.cumulative_lost(), 0);
+}
+
+#[test]
+fn test_receiver_stats_jitter() {
+    // packets sent every 20ms and received with a constant delay have no jitter
+    let mut stats = ReceiverStats::new(0x1234ABCD, 8000);
+    for i in 0..10u16 {
+        stats.update(
+            &header(i, (u32::MAX - 320).wrapping_add(160 * i as u32)),
+            at(20 * i as u64),
+        );
+    }
+    assert_eq!(stats.jitter(), 0);
+
+    // each packet arrives 10ms, i.e. 80 timestamp units, late or early
+    let mut stats = ReceiverStats::new(0x1234ABCD, 8000);
+    for i in 0..200u16 {
+        let delay = if i % 2 == 0 { 0 } else { 10 };
+        stats.update(&header(i, 160 * i as u32), at(20 * i as u64 + delay));
+    }
+    assert_eq!(stats.jitter(), 79);
+}
+
+#[test]
+fn test_receiver_stats_sender_report() {
+    let mut stats = ReceiverStats::new(0x1234ABCD, 90000);
+    stats.update(&header(1, 0), at(0));
+    stats.update(&header(2, 0), at(0));
+
+    let report = stats.report(at(0));
+    assert_eq!(report.last_sender_report, 0);
+    assert_eq!(report.delay, 0);
+
+    stats.on_sender_report(0x0123_4567_89AB_CDEF, at(1000));
+    let report = stats.report(at(1500));
+    assert_eq!(report.last_sender_report, 0x4567_89AB);
+    assert_eq!(report.delay, 32768);
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod sender_stats_test;
+
+use crate::extension::abs_send_time_extension::{ntp2unix, unix2ntp};
+use crate::packet::Packet;
+
+use std::time::SystemTime;
+
+/// SenderInfo holds the sender information of an RTCP sender report
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SenderInfo {
+    pub ntp_time: u64,
+    pub rtp_time: u32,
+    pub packet_count: u32,
+    pub octet_count: u32,
+}
+
+/// SenderStats counts the packets and payload octets sent on an RTP stream and
+/// relates its RTP timestamps to the wallclock
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SenderStats {
+    clock_rate: u32,
+    packet_count: u32,
+    octet_count: u32,
+    last_timestamp: Option<(u32, SystemTime)>,
+}
+
+impl SenderStats {
+    pub fn new(clock_rate: u32) -> Self {
+        SenderStats {
+            clock_rate,
+            ..Default::default()
+        }
+    }
+
+    /// update records a packet sent at send_time. Octets count the payload
+    /// only, not the header or the padding. Padding-only packets are counted but
+    /// do not anchor the RTP timestamp, as they may carry the timestamp of the
+    /// next payload.
+    pub fn update(&mut self, packet: &Packet, send_time: SystemTime) {
+        self.packet_count = self.packet_count.wrapping_add(1);
+        self.octet_count = self.octet_count.wrapping_add(packet.payload.len() as u32);
+        if !packet.payload.is_empty() {
+            self.last_timestamp = Some((packet.header.timestamp, send_time));
+        }
+    }
+
+    /// packet_count returns the number of packets sent, wrapping at 32 bits
+    pub fn packet_count(&self) -> u32 {
+        self.packet_count
+    }
+
+    /// octet_count returns the number of payload octets sent, wrapping at 32 bits
+    pub fn octet_count(&self) -> u32 {
+        self.octet_count
+    }
+
+    /// last_timestamp returns the RTP timestamp of the last packet sent and the
+    /// time it was sent
+    pub fn last_timestamp(&self) -> Option<(u32, SystemTime)> {
+        self.last_timestamp
+    }
+
+    /// rtp_timestamp_at extrapolates the RTP timestamp of an NTP time from the
+    /// last packet sent, using the clock rate
+    pub fn rtp_timestamp_at(&self, ntp_time: u64) -> Option<u32> {
+        self.rtp_timestamp_at_time(ntp2unix(ntp_time))
+    }
+
+    fn rtp_timestamp_at_time(&self, t: SystemTime) -> Option<u32> {
+        let (timestamp, send_time) = self.last_timestamp?;
+        let ticks = |nanos: u128| (nanos * self.clock_rate as u128 / 1_000_000_000) as u32;
+        // wrapping arithmetic wraps the timestamp around
+        Some(match t.duration_since(send_time) {
+            Ok(elapsed) => timestamp.wrapping_add(ticks(elapsed.as_nanos())),
+            Err(err) => timestamp.wrapping_sub(ticks(err.duration().as_nanos())),
+        })
+    }
+
+    /// sender_info returns the sender information of a sender report sent at
+    /// now, once a packet has been sent
+    pub fn sender_info(&self, now: SystemTime) -> Option<SenderInfo> {
+        Some(SenderInfo {
+            ntp_time: unix2ntp(now),
+            rtp_time: self.rtp_timestamp_at_time(now)?,
+            packet_count: self.packet_count,
+            octet_count: self.octet_count,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use super::*;
+use crate::test_util::{at, packet};
+
+use std::time::Duration;
+
+#[test]
+fn test_sender_stats() {
+    let mut stats = SenderStats::new(90000);
+    assert_eq!(stats.rtp_timestamp_at(unix2ntp(at(0))), None);
+    assert_eq!(stats.sender_info(at(0)), None);
+
+    stats.update(&packet(0, 0, u32::MAX - 899, &[0; 100]), at(0));
+    stats.update(&packet(0, 0, u32::MAX - 899, &[0; 50]), at(0));
+    assert_eq!(stats.packet_count(), 2);
+    assert_eq!(stats.octet_count(), 150);
+    assert_eq!(stats.last_timestamp(), Some((u32::MAX - 899, at(0))));
+
+    // padding-only packets are counted but keep the anchor
+    stats.update(&packet(0, 0, 3000, &[]), at(10));
+    assert_eq!(stats.packet_count(), 3);
+    assert_eq!(stats.last_timestamp(), Some((u32::MAX - 899, at(0))));
+
+    // extrapolated forward across the wrap and backward
+    assert_eq!(stats.rtp_timestamp_at(unix2ntp(at(500))), Some(44100));
+    assert_eq!(
+        stats.rtp_timestamp_at(unix2ntp(at(0) - Duration::from_millis(250))),
+        Some(u32::MAX - 23399)
+    );
+
+    let info = stats.sender_info(at(1000)).unwrap();
+    assert_eq!(
+        info,
+        SenderInfo {
+            ntp_time: unix2ntp(at(1000)),
+            rtp_time: 89100,
+            packet_count: 3,
+            octet_count: 150,
+        }
+    );
+}
@@ -0,0 +1,28 @@
+//! Fixtures shared by the unit tests
+
+use crate::header::Header;
+use crate::packet::Packet;
+
+use bytes::Bytes;
+use std::time::{Duration, SystemTime};
+
+/// at returns a fixed point in time advanced by millis
+pub(crate) fn at(millis: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000) + Duration::from_millis(millis)
+}
+
+/// packet returns a version 2 packet with the given SSRC, sequence number,
+/// timestamp and payload
+pub(crate) fn packet(ssrc: u32, sequence_number: u16, timestamp: u32, payload: &[u8]) -> Packet {
+    Packet {
+        header: Header {
+            version: 2,
+            ssrc,
+            sequence_number,
+            timestamp,
+            ..Default::default()
+        },
+        payload: Bytes::copy_from_slice(payload),
+        ..Default::default()
+    }
+}